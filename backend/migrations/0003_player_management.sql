@@ -0,0 +1,8 @@
+-- Player names identify members in the UI, so keep them unique
+CREATE UNIQUE INDEX IF NOT EXISTS players_name_key ON players (name);
+
+-- Deleting a player must not silently wipe their game history
+ALTER TABLE game_player_results
+  DROP CONSTRAINT IF EXISTS game_player_results_player_id_fkey,
+  ADD CONSTRAINT game_player_results_player_id_fkey
+    FOREIGN KEY (player_id) REFERENCES players (id) ON DELETE RESTRICT;
@@ -0,0 +1,13 @@
+-- Keep the updated column current on every row change
+CREATE OR REPLACE FUNCTION set_updated_timestamp () RETURNS TRIGGER AS $$
+BEGIN
+  NEW.updated = NOW();
+  RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER tournaments_set_updated BEFORE UPDATE ON tournaments
+FOR EACH ROW EXECUTE FUNCTION set_updated_timestamp ();
+
+CREATE TRIGGER players_set_updated BEFORE UPDATE ON players
+FOR EACH ROW EXECUTE FUNCTION set_updated_timestamp ();
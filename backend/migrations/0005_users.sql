@@ -0,0 +1,13 @@
+-- Accounts allowed to sign in; write endpoints are gated by role
+CREATE TABLE
+  IF NOT EXISTS users (
+    id SERIAL PRIMARY KEY,
+    username TEXT NOT NULL UNIQUE,
+    password_hash TEXT NOT NULL,
+    role TEXT NOT NULL CHECK (role IN ('viewer', 'scorekeeper', 'admin')),
+    created TIMESTAMPTZ NOT NULL DEFAULT NOW (),
+    updated TIMESTAMPTZ NOT NULL DEFAULT NOW ()
+  );
+
+CREATE TRIGGER users_set_updated BEFORE UPDATE ON users
+FOR EACH ROW EXECUTE FUNCTION set_updated_timestamp ();
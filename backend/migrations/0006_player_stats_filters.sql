@@ -0,0 +1,64 @@
+-- Player statistics over a subset of games. Every filter is optional; NULL
+-- means no restriction. The player_stats view is the unfiltered case.
+CREATE OR REPLACE FUNCTION player_stats_filtered (
+  date_from DATE,
+  date_to DATE,
+  tournament_ids INTEGER[],
+  series TEXT
+) RETURNS TABLE (
+  player_id INTEGER,
+  player_name TEXT,
+  game_count BIGINT,
+  total_gp BIGINT,
+  total_tp FLOAT4,
+  total_pp FLOAT4,
+  first_place_count BIGINT,
+  second_place_count BIGINT,
+  third_place_count BIGINT,
+  fourth_place_count BIGINT,
+  avg_gp FLOAT4,
+  avg_tp FLOAT4,
+  avg_pp FLOAT4,
+  first_place_ratio FLOAT4,
+  second_place_ratio FLOAT4,
+  third_place_ratio FLOAT4,
+  fourth_place_ratio FLOAT4
+) AS $$
+SELECT
+  p.id AS player_id,
+  p.name AS player_name,
+  COUNT(gpr.id) AS game_count,
+  SUM(gpr.game_point) AS total_gp,
+  SUM(gpr.table_point)::FLOAT4 AS total_tp,
+  SUM(gpr.table_point * 2 - 5)::FLOAT4 AS total_pp,
+  COUNT(*) FILTER (WHERE gpr.table_point = 4) AS first_place_count,
+  COUNT(*) FILTER (WHERE gpr.table_point = 3) AS second_place_count,
+  COUNT(*) FILTER (WHERE gpr.table_point = 2) AS third_place_count,
+  COUNT(*) FILTER (WHERE gpr.table_point = 1) AS fourth_place_count,
+  AVG(gpr.game_point)::FLOAT4 AS avg_gp,
+  AVG(gpr.table_point)::FLOAT4 AS avg_tp,
+  AVG(gpr.table_point * 2 - 5)::FLOAT4 AS avg_pp,
+  ROUND(100.0 * COUNT(*) FILTER (WHERE gpr.table_point = 4) / NULLIF(COUNT(*),0), 2)::FLOAT4 AS first_place_ratio,
+  ROUND(100.0 * COUNT(*) FILTER (WHERE gpr.table_point = 3) / NULLIF(COUNT(*),0), 2)::FLOAT4 AS second_place_ratio,
+  ROUND(100.0 * COUNT(*) FILTER (WHERE gpr.table_point = 2) / NULLIF(COUNT(*),0), 2)::FLOAT4 AS third_place_ratio,
+  ROUND(100.0 * COUNT(*) FILTER (WHERE gpr.table_point = 1) / NULLIF(COUNT(*),0), 2)::FLOAT4 AS fourth_place_ratio
+FROM
+  players p
+  LEFT JOIN (
+    SELECT gpr.*
+    FROM game_player_results gpr
+    JOIN games g ON g.id = gpr.game_id
+    JOIN sessions s ON s.id = g.session_id
+    JOIN tournaments t ON t.id = s.tournament_id
+    WHERE (date_from IS NULL OR t.date >= date_from)
+      AND (date_to IS NULL OR t.date <= date_to)
+      AND (tournament_ids IS NULL OR t.id = ANY (tournament_ids))
+      AND (series IS NULL OR t.sub_name = series)
+  ) gpr ON p.id = gpr.player_id
+GROUP BY
+  p.id,
+  p.name;
+$$ LANGUAGE sql STABLE;
+
+CREATE OR REPLACE VIEW player_stats AS
+SELECT * FROM player_stats_filtered (NULL, NULL, NULL, NULL);
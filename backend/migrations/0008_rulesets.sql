@@ -0,0 +1,104 @@
+-- Scoring rules per tournament. Place points are the uma for the finishing
+-- position plus, for first place, the oka: (return_points - starting_points)
+-- * 4 / 10000. Uma and oka are in units of 10,000 points (100 game points),
+-- while starting_points and return_points are plain points, so 25000/30000
+-- with uma {3, 1, -1, -3} pays first place 3 + 2. Rulesets are not edited
+-- once created.
+CREATE TABLE
+  IF NOT EXISTS rulesets (
+    id SERIAL PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    starting_points INTEGER NOT NULL CHECK (starting_points > 0),
+    return_points INTEGER NOT NULL CHECK (return_points > 0),
+    uma FLOAT4[] NOT NULL CHECK (array_length(uma, 1) = 4),
+    tie_handling TEXT NOT NULL DEFAULT 'split' CHECK (tie_handling IN ('split', 'seat_order')),
+    created TIMESTAMPTZ NOT NULL DEFAULT NOW ()
+  );
+
+-- The rules every existing result was scored under: table_point * 2 - 5
+INSERT INTO rulesets (name, starting_points, return_points, uma, tie_handling)
+VALUES ('standard', 25000, 25000, '{3, 1, -1, -3}', 'split');
+
+-- New tournaments without an explicit ruleset use the first one
+ALTER TABLE tournaments ADD COLUMN ruleset_id INTEGER REFERENCES rulesets (id) ON DELETE RESTRICT;
+UPDATE tournaments SET ruleset_id = (SELECT id FROM rulesets WHERE name = 'standard');
+ALTER TABLE tournaments ALTER COLUMN ruleset_id SET NOT NULL;
+
+-- Place points are derived from the tournament's ruleset when a game is
+-- written, like table points
+ALTER TABLE game_player_results ADD COLUMN place_point FLOAT4;
+UPDATE game_player_results SET place_point = table_point * 2 - 5;
+ALTER TABLE game_player_results ALTER COLUMN place_point SET NOT NULL;
+
+CREATE OR REPLACE FUNCTION player_stats_filtered (
+  date_from DATE,
+  date_to DATE,
+  tournament_ids INTEGER[],
+  series TEXT
+) RETURNS TABLE (
+  player_id INTEGER,
+  player_name TEXT,
+  game_count BIGINT,
+  total_gp BIGINT,
+  total_tp FLOAT4,
+  total_pp FLOAT4,
+  first_place_count FLOAT4,
+  second_place_count FLOAT4,
+  third_place_count FLOAT4,
+  fourth_place_count FLOAT4,
+  shared_place_count BIGINT,
+  avg_gp FLOAT4,
+  avg_tp FLOAT4,
+  avg_pp FLOAT4,
+  first_place_ratio FLOAT4,
+  second_place_ratio FLOAT4,
+  third_place_ratio FLOAT4,
+  fourth_place_ratio FLOAT4
+) AS $$
+SELECT
+  p.id AS player_id,
+  p.name AS player_name,
+  COUNT(gpr.id) AS game_count,
+  SUM(gpr.game_point) AS total_gp,
+  SUM(gpr.table_point)::FLOAT4 AS total_tp,
+  SUM(gpr.place_point)::FLOAT4 AS total_pp,
+  COALESCE(SUM(gpr.first_credit), 0)::FLOAT4 AS first_place_count,
+  COALESCE(SUM(gpr.second_credit), 0)::FLOAT4 AS second_place_count,
+  COALESCE(SUM(gpr.third_credit), 0)::FLOAT4 AS third_place_count,
+  COALESCE(SUM(gpr.fourth_credit), 0)::FLOAT4 AS fourth_place_count,
+  COUNT(*) FILTER (WHERE gpr.tied_players > 1) AS shared_place_count,
+  AVG(gpr.game_point)::FLOAT4 AS avg_gp,
+  AVG(gpr.table_point)::FLOAT4 AS avg_tp,
+  AVG(gpr.place_point)::FLOAT4 AS avg_pp,
+  ROUND(100.0 * COALESCE(SUM(gpr.first_credit), 0) / NULLIF(COUNT(*),0), 2)::FLOAT4 AS first_place_ratio,
+  ROUND(100.0 * COALESCE(SUM(gpr.second_credit), 0) / NULLIF(COUNT(*),0), 2)::FLOAT4 AS second_place_ratio,
+  ROUND(100.0 * COALESCE(SUM(gpr.third_credit), 0) / NULLIF(COUNT(*),0), 2)::FLOAT4 AS third_place_ratio,
+  ROUND(100.0 * COALESCE(SUM(gpr.fourth_credit), 0) / NULLIF(COUNT(*),0), 2)::FLOAT4 AS fourth_place_ratio
+FROM
+  players p
+  LEFT JOIN (
+    SELECT
+      placed.*,
+      CASE WHEN 1 BETWEEN place_from AND place_from + tied_players - 1 THEN 1.0 / tied_players ELSE 0 END AS first_credit,
+      CASE WHEN 2 BETWEEN place_from AND place_from + tied_players - 1 THEN 1.0 / tied_players ELSE 0 END AS second_credit,
+      CASE WHEN 3 BETWEEN place_from AND place_from + tied_players - 1 THEN 1.0 / tied_players ELSE 0 END AS third_credit,
+      CASE WHEN 4 BETWEEN place_from AND place_from + tied_players - 1 THEN 1.0 / tied_players ELSE 0 END AS fourth_credit
+    FROM (
+      SELECT
+        gpr.*,
+        RANK() OVER (PARTITION BY gpr.game_id ORDER BY gpr.table_point DESC) AS place_from,
+        COUNT(*) OVER (PARTITION BY gpr.game_id, gpr.table_point) AS tied_players
+      FROM game_player_results gpr
+      JOIN games g ON g.id = gpr.game_id
+      JOIN sessions s ON s.id = g.session_id
+      JOIN tournaments t ON t.id = s.tournament_id
+      WHERE (date_from IS NULL OR t.date >= date_from)
+        AND (date_to IS NULL OR t.date <= date_to)
+        AND (tournament_ids IS NULL OR t.id = ANY (tournament_ids))
+        AND (series IS NULL OR t.sub_name = series)
+    ) placed
+  ) gpr ON p.id = gpr.player_id
+GROUP BY
+  p.id,
+  p.name;
+$$ LANGUAGE sql STABLE;
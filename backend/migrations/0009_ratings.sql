@@ -0,0 +1,16 @@
+-- Glicko-2 ratings, one row per player per game, holding the rating after
+-- that game. Ratings depend on every earlier game, so rows are rebuilt by
+-- the backend (on game writes, or with `backend rebuild-ratings`) rather
+-- than computed here; run that command once after applying this migration.
+CREATE TABLE
+  IF NOT EXISTS player_ratings (
+    game_id INTEGER NOT NULL REFERENCES games (id) ON DELETE CASCADE,
+    player_id INTEGER NOT NULL REFERENCES players (id) ON DELETE CASCADE,
+    rating FLOAT8 NOT NULL,
+    deviation FLOAT8 NOT NULL,
+    volatility FLOAT8 NOT NULL,
+    rating_change FLOAT8 NOT NULL,
+    PRIMARY KEY (game_id, player_id)
+  );
+
+CREATE INDEX IF NOT EXISTS player_ratings_player_id_idx ON player_ratings (player_id);
@@ -0,0 +1,47 @@
+-- Seasons group tournaments for standings across them. A tournament may
+-- belong to several seasons (e.g. a league season and a calendar year).
+-- Scoring is either the sum of table points, or league points awarded by
+-- tournament_place (league_points[1] for 1st, nothing past the array's end).
+-- Each player's worst drop_worst tournaments are left out, and players who
+-- attended fewer than min_attendance tournaments are not ranked.
+CREATE TABLE
+  IF NOT EXISTS seasons (
+    id SERIAL PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    scoring TEXT NOT NULL DEFAULT 'table_points' CHECK (scoring IN ('table_points', 'league_points')),
+    league_points FLOAT4[] NOT NULL DEFAULT '{}',
+    drop_worst INTEGER NOT NULL DEFAULT 0 CHECK (drop_worst >= 0),
+    min_attendance INTEGER NOT NULL DEFAULT 0 CHECK (min_attendance >= 0),
+    created TIMESTAMPTZ NOT NULL DEFAULT NOW (),
+    updated TIMESTAMPTZ NOT NULL DEFAULT NOW ()
+  );
+
+CREATE TRIGGER seasons_set_updated BEFORE UPDATE ON seasons
+FOR EACH ROW EXECUTE FUNCTION set_updated_timestamp ();
+
+CREATE TABLE
+  IF NOT EXISTS season_tournaments (
+    season_id INTEGER NOT NULL REFERENCES seasons (id) ON DELETE CASCADE,
+    tournament_id INTEGER NOT NULL REFERENCES tournaments (id) ON DELETE CASCADE,
+    PRIMARY KEY (season_id, tournament_id)
+  );
+
+CREATE INDEX IF NOT EXISTS season_tournaments_tournament_id_idx ON season_tournaments (tournament_id);
+
+-- Series that ran over several tournaments become seasons of their own
+INSERT INTO seasons (name)
+SELECT sub_name FROM tournaments GROUP BY sub_name HAVING COUNT(*) > 1;
+
+INSERT INTO season_tournaments (season_id, tournament_id)
+SELECT s.id, t.id FROM seasons s JOIN tournaments t ON t.sub_name = s.name;
+
+-- Every calendar year with tournaments becomes a season too, e.g. '2023年'
+INSERT INTO seasons (name)
+SELECT DISTINCT EXTRACT(YEAR FROM date)::INTEGER || '年' FROM tournaments
+ON CONFLICT (name) DO NOTHING;
+
+INSERT INTO season_tournaments (season_id, tournament_id)
+SELECT s.id, t.id
+FROM seasons s
+JOIN tournaments t ON s.name = EXTRACT(YEAR FROM t.date)::INTEGER || '年'
+ON CONFLICT DO NOTHING;
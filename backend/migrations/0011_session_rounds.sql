@@ -0,0 +1,18 @@
+-- Sessions (rounds) get an explicit position within their tournament instead
+-- of being ordered by id. Existing sessions are numbered in id order, which
+-- is the order they have always been shown in. The round number constraint
+-- is deferrable so rounds can be renumbered in one transaction.
+ALTER TABLE sessions ADD COLUMN round_number INTEGER;
+
+UPDATE sessions s
+SET round_number = numbered.round_number
+FROM (
+  SELECT id, ROW_NUMBER() OVER (PARTITION BY tournament_id ORDER BY id) AS round_number
+  FROM sessions
+) numbered
+WHERE numbered.id = s.id;
+
+ALTER TABLE sessions ALTER COLUMN round_number SET NOT NULL;
+ALTER TABLE sessions ADD CONSTRAINT sessions_round_number_check CHECK (round_number > 0);
+ALTER TABLE sessions ADD CONSTRAINT sessions_tournament_round_key UNIQUE (tournament_id, round_number) DEFERRABLE INITIALLY IMMEDIATE;
+ALTER TABLE sessions ADD CONSTRAINT sessions_tournament_name_key UNIQUE (tournament_id, name);
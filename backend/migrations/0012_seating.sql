@@ -0,0 +1,124 @@
+-- Seating used to be implied by insertion order (gpr.id): results were
+-- entered East, South, West, North. Make the seat wind explicit, backfilled
+-- from that order, and record which table a game was played at. Table
+-- numbers of games recorded before this are unknown and stay NULL.
+ALTER TABLE games ADD COLUMN table_no INTEGER CHECK (table_no > 0);
+
+ALTER TABLE game_player_results ADD COLUMN seat_wind TEXT CHECK (seat_wind IN ('east', 'south', 'west', 'north'));
+
+UPDATE game_player_results gpr
+SET seat_wind = (ARRAY['east', 'south', 'west', 'north'])[seated.seat]
+FROM (
+  SELECT id, ROW_NUMBER() OVER (PARTITION BY game_id ORDER BY id) AS seat
+  FROM game_player_results
+) seated
+WHERE seated.id = gpr.id;
+
+ALTER TABLE game_player_results ALTER COLUMN seat_wind SET NOT NULL;
+ALTER TABLE game_player_results ADD CONSTRAINT game_player_results_game_seat_key UNIQUE (game_id, seat_wind);
+
+-- Per-seat figures by starting wind. Places are read from table points
+-- (5 - table_point), so a shared placement counts as the average of the
+-- places it spans.
+DROP VIEW IF EXISTS player_stats;
+DROP FUNCTION IF EXISTS player_stats_filtered (DATE, DATE, INTEGER[], TEXT);
+
+CREATE FUNCTION player_stats_filtered (
+  date_from DATE,
+  date_to DATE,
+  tournament_ids INTEGER[],
+  series TEXT
+) RETURNS TABLE (
+  player_id INTEGER,
+  player_name TEXT,
+  game_count BIGINT,
+  total_gp BIGINT,
+  total_tp FLOAT4,
+  total_pp FLOAT4,
+  first_place_count FLOAT4,
+  second_place_count FLOAT4,
+  third_place_count FLOAT4,
+  fourth_place_count FLOAT4,
+  shared_place_count BIGINT,
+  avg_gp FLOAT4,
+  avg_tp FLOAT4,
+  avg_pp FLOAT4,
+  first_place_ratio FLOAT4,
+  second_place_ratio FLOAT4,
+  third_place_ratio FLOAT4,
+  fourth_place_ratio FLOAT4,
+  east_game_count BIGINT,
+  east_avg_place FLOAT4,
+  east_avg_gp FLOAT4,
+  south_game_count BIGINT,
+  south_avg_place FLOAT4,
+  south_avg_gp FLOAT4,
+  west_game_count BIGINT,
+  west_avg_place FLOAT4,
+  west_avg_gp FLOAT4,
+  north_game_count BIGINT,
+  north_avg_place FLOAT4,
+  north_avg_gp FLOAT4
+) AS $$
+SELECT
+  p.id AS player_id,
+  p.name AS player_name,
+  COUNT(gpr.id) AS game_count,
+  SUM(gpr.game_point) AS total_gp,
+  SUM(gpr.table_point)::FLOAT4 AS total_tp,
+  SUM(gpr.place_point)::FLOAT4 AS total_pp,
+  COALESCE(SUM(gpr.first_credit), 0)::FLOAT4 AS first_place_count,
+  COALESCE(SUM(gpr.second_credit), 0)::FLOAT4 AS second_place_count,
+  COALESCE(SUM(gpr.third_credit), 0)::FLOAT4 AS third_place_count,
+  COALESCE(SUM(gpr.fourth_credit), 0)::FLOAT4 AS fourth_place_count,
+  COUNT(*) FILTER (WHERE gpr.tied_players > 1) AS shared_place_count,
+  AVG(gpr.game_point)::FLOAT4 AS avg_gp,
+  AVG(gpr.table_point)::FLOAT4 AS avg_tp,
+  AVG(gpr.place_point)::FLOAT4 AS avg_pp,
+  ROUND(100.0 * COALESCE(SUM(gpr.first_credit), 0) / NULLIF(COUNT(*),0), 2)::FLOAT4 AS first_place_ratio,
+  ROUND(100.0 * COALESCE(SUM(gpr.second_credit), 0) / NULLIF(COUNT(*),0), 2)::FLOAT4 AS second_place_ratio,
+  ROUND(100.0 * COALESCE(SUM(gpr.third_credit), 0) / NULLIF(COUNT(*),0), 2)::FLOAT4 AS third_place_ratio,
+  ROUND(100.0 * COALESCE(SUM(gpr.fourth_credit), 0) / NULLIF(COUNT(*),0), 2)::FLOAT4 AS fourth_place_ratio,
+  COUNT(*) FILTER (WHERE gpr.seat_wind = 'east') AS east_game_count,
+  AVG(5 - gpr.table_point) FILTER (WHERE gpr.seat_wind = 'east')::FLOAT4 AS east_avg_place,
+  AVG(gpr.game_point) FILTER (WHERE gpr.seat_wind = 'east')::FLOAT4 AS east_avg_gp,
+  COUNT(*) FILTER (WHERE gpr.seat_wind = 'south') AS south_game_count,
+  AVG(5 - gpr.table_point) FILTER (WHERE gpr.seat_wind = 'south')::FLOAT4 AS south_avg_place,
+  AVG(gpr.game_point) FILTER (WHERE gpr.seat_wind = 'south')::FLOAT4 AS south_avg_gp,
+  COUNT(*) FILTER (WHERE gpr.seat_wind = 'west') AS west_game_count,
+  AVG(5 - gpr.table_point) FILTER (WHERE gpr.seat_wind = 'west')::FLOAT4 AS west_avg_place,
+  AVG(gpr.game_point) FILTER (WHERE gpr.seat_wind = 'west')::FLOAT4 AS west_avg_gp,
+  COUNT(*) FILTER (WHERE gpr.seat_wind = 'north') AS north_game_count,
+  AVG(5 - gpr.table_point) FILTER (WHERE gpr.seat_wind = 'north')::FLOAT4 AS north_avg_place,
+  AVG(gpr.game_point) FILTER (WHERE gpr.seat_wind = 'north')::FLOAT4 AS north_avg_gp
+FROM
+  players p
+  LEFT JOIN (
+    SELECT
+      placed.*,
+      CASE WHEN 1 BETWEEN place_from AND place_from + tied_players - 1 THEN 1.0 / tied_players ELSE 0 END AS first_credit,
+      CASE WHEN 2 BETWEEN place_from AND place_from + tied_players - 1 THEN 1.0 / tied_players ELSE 0 END AS second_credit,
+      CASE WHEN 3 BETWEEN place_from AND place_from + tied_players - 1 THEN 1.0 / tied_players ELSE 0 END AS third_credit,
+      CASE WHEN 4 BETWEEN place_from AND place_from + tied_players - 1 THEN 1.0 / tied_players ELSE 0 END AS fourth_credit
+    FROM (
+      SELECT
+        gpr.*,
+        RANK() OVER (PARTITION BY gpr.game_id ORDER BY gpr.table_point DESC) AS place_from,
+        COUNT(*) OVER (PARTITION BY gpr.game_id, gpr.table_point) AS tied_players
+      FROM game_player_results gpr
+      JOIN games g ON g.id = gpr.game_id
+      JOIN sessions s ON s.id = g.session_id
+      JOIN tournaments t ON t.id = s.tournament_id
+      WHERE (date_from IS NULL OR t.date >= date_from)
+        AND (date_to IS NULL OR t.date <= date_to)
+        AND (tournament_ids IS NULL OR t.id = ANY (tournament_ids))
+        AND (series IS NULL OR t.sub_name = series)
+    ) placed
+  ) gpr ON p.id = gpr.player_id
+GROUP BY
+  p.id,
+  p.name;
+$$ LANGUAGE sql STABLE;
+
+CREATE VIEW player_stats AS
+SELECT * FROM player_stats_filtered (NULL, NULL, NULL, NULL);
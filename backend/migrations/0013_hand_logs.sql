@@ -0,0 +1,33 @@
+-- Optional hand-by-hand (kyoku) log of a game. Seats are the players'
+-- starting winds (game_player_results.seat_wind); the dealer of hand N is
+-- the player who started in seat N.
+CREATE TABLE
+  IF NOT EXISTS game_hands (
+    id SERIAL PRIMARY KEY,
+    game_id INTEGER NOT NULL REFERENCES games (id) ON DELETE CASCADE,
+    -- Position of the hand within the game, from 1
+    hand_index INTEGER NOT NULL CHECK (hand_index > 0),
+    round_wind TEXT NOT NULL CHECK (round_wind IN ('east', 'south', 'west', 'north')),
+    hand_number INTEGER NOT NULL CHECK (hand_number BETWEEN 1 AND 4),
+    honba INTEGER NOT NULL DEFAULT 0 CHECK (honba >= 0),
+    -- Riichi deposits on the table when the hand starts
+    riichi_sticks INTEGER NOT NULL DEFAULT 0 CHECK (riichi_sticks >= 0),
+    outcome TEXT NOT NULL CHECK (outcome IN ('ron', 'tsumo', 'draw', 'abortive_draw', 'chombo')),
+    discarder_seat TEXT CHECK (discarder_seat IN ('east', 'south', 'west', 'north')),
+    chombo_seat TEXT CHECK (chombo_seat IN ('east', 'south', 'west', 'north')),
+    CONSTRAINT game_hands_game_hand_key UNIQUE (game_id, hand_index)
+  );
+
+-- One row per seat per hand. Winners are the seats with han set.
+CREATE TABLE
+  IF NOT EXISTS game_hand_seats (
+    hand_id INTEGER NOT NULL REFERENCES game_hands (id) ON DELETE CASCADE,
+    seat_wind TEXT NOT NULL CHECK (seat_wind IN ('east', 'south', 'west', 'north')),
+    -- Net change of the seat's score, riichi deposits included
+    point_change INTEGER NOT NULL,
+    riichi BOOLEAN NOT NULL DEFAULT FALSE,
+    tenpai BOOLEAN NOT NULL DEFAULT FALSE,
+    han INTEGER CHECK (han > 0),
+    fu INTEGER CHECK (fu > 0),
+    PRIMARY KEY (hand_id, seat_wind)
+  );
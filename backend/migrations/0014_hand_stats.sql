@@ -0,0 +1,179 @@
+-- Whether a player opened their hand with a call (chi, pon or open kan)
+ALTER TABLE game_hand_seats ADD COLUMN called BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Hand-level figures from the hand logs. Rates are percentages of the hands
+-- the player sat in; games without a log do not count towards them. Win and
+-- deal-in values leave out riichi deposits.
+DROP VIEW IF EXISTS player_stats;
+DROP FUNCTION IF EXISTS player_stats_filtered (DATE, DATE, INTEGER[], TEXT);
+
+CREATE FUNCTION player_stats_filtered (
+  date_from DATE,
+  date_to DATE,
+  tournament_ids INTEGER[],
+  series TEXT
+) RETURNS TABLE (
+  player_id INTEGER,
+  player_name TEXT,
+  game_count BIGINT,
+  total_gp BIGINT,
+  total_tp FLOAT4,
+  total_pp FLOAT4,
+  first_place_count FLOAT4,
+  second_place_count FLOAT4,
+  third_place_count FLOAT4,
+  fourth_place_count FLOAT4,
+  shared_place_count BIGINT,
+  avg_gp FLOAT4,
+  avg_tp FLOAT4,
+  avg_pp FLOAT4,
+  first_place_ratio FLOAT4,
+  second_place_ratio FLOAT4,
+  third_place_ratio FLOAT4,
+  fourth_place_ratio FLOAT4,
+  east_game_count BIGINT,
+  east_avg_place FLOAT4,
+  east_avg_gp FLOAT4,
+  south_game_count BIGINT,
+  south_avg_place FLOAT4,
+  south_avg_gp FLOAT4,
+  west_game_count BIGINT,
+  west_avg_place FLOAT4,
+  west_avg_gp FLOAT4,
+  north_game_count BIGINT,
+  north_avg_place FLOAT4,
+  north_avg_gp FLOAT4,
+  hand_count BIGINT,
+  agari_rate FLOAT4,
+  houjuu_rate FLOAT4,
+  riichi_rate FLOAT4,
+  call_rate FLOAT4,
+  avg_win_value FLOAT4,
+  avg_deal_in_value FLOAT4,
+  ryuukyoku_tenpai_rate FLOAT4,
+  tsumo_ratio FLOAT4
+) AS $$
+SELECT
+  placement.*,
+  hands.hand_count,
+  hands.agari_rate,
+  hands.houjuu_rate,
+  hands.riichi_rate,
+  hands.call_rate,
+  hands.avg_win_value,
+  hands.avg_deal_in_value,
+  hands.ryuukyoku_tenpai_rate,
+  hands.tsumo_ratio
+FROM
+  (
+    SELECT
+      p.id AS player_id,
+      p.name AS player_name,
+      COUNT(gpr.id) AS game_count,
+      SUM(gpr.game_point) AS total_gp,
+      SUM(gpr.table_point)::FLOAT4 AS total_tp,
+      SUM(gpr.place_point)::FLOAT4 AS total_pp,
+      COALESCE(SUM(gpr.first_credit), 0)::FLOAT4 AS first_place_count,
+      COALESCE(SUM(gpr.second_credit), 0)::FLOAT4 AS second_place_count,
+      COALESCE(SUM(gpr.third_credit), 0)::FLOAT4 AS third_place_count,
+      COALESCE(SUM(gpr.fourth_credit), 0)::FLOAT4 AS fourth_place_count,
+      COUNT(*) FILTER (WHERE gpr.tied_players > 1) AS shared_place_count,
+      AVG(gpr.game_point)::FLOAT4 AS avg_gp,
+      AVG(gpr.table_point)::FLOAT4 AS avg_tp,
+      AVG(gpr.place_point)::FLOAT4 AS avg_pp,
+      ROUND(100.0 * COALESCE(SUM(gpr.first_credit), 0) / NULLIF(COUNT(*),0), 2)::FLOAT4 AS first_place_ratio,
+      ROUND(100.0 * COALESCE(SUM(gpr.second_credit), 0) / NULLIF(COUNT(*),0), 2)::FLOAT4 AS second_place_ratio,
+      ROUND(100.0 * COALESCE(SUM(gpr.third_credit), 0) / NULLIF(COUNT(*),0), 2)::FLOAT4 AS third_place_ratio,
+      ROUND(100.0 * COALESCE(SUM(gpr.fourth_credit), 0) / NULLIF(COUNT(*),0), 2)::FLOAT4 AS fourth_place_ratio,
+      COUNT(*) FILTER (WHERE gpr.seat_wind = 'east') AS east_game_count,
+      AVG(5 - gpr.table_point) FILTER (WHERE gpr.seat_wind = 'east')::FLOAT4 AS east_avg_place,
+      AVG(gpr.game_point) FILTER (WHERE gpr.seat_wind = 'east')::FLOAT4 AS east_avg_gp,
+      COUNT(*) FILTER (WHERE gpr.seat_wind = 'south') AS south_game_count,
+      AVG(5 - gpr.table_point) FILTER (WHERE gpr.seat_wind = 'south')::FLOAT4 AS south_avg_place,
+      AVG(gpr.game_point) FILTER (WHERE gpr.seat_wind = 'south')::FLOAT4 AS south_avg_gp,
+      COUNT(*) FILTER (WHERE gpr.seat_wind = 'west') AS west_game_count,
+      AVG(5 - gpr.table_point) FILTER (WHERE gpr.seat_wind = 'west')::FLOAT4 AS west_avg_place,
+      AVG(gpr.game_point) FILTER (WHERE gpr.seat_wind = 'west')::FLOAT4 AS west_avg_gp,
+      COUNT(*) FILTER (WHERE gpr.seat_wind = 'north') AS north_game_count,
+      AVG(5 - gpr.table_point) FILTER (WHERE gpr.seat_wind = 'north')::FLOAT4 AS north_avg_place,
+      AVG(gpr.game_point) FILTER (WHERE gpr.seat_wind = 'north')::FLOAT4 AS north_avg_gp
+    FROM
+      players p
+      LEFT JOIN (
+        SELECT
+          placed.*,
+          CASE WHEN 1 BETWEEN place_from AND place_from + tied_players - 1 THEN 1.0 / tied_players ELSE 0 END AS first_credit,
+          CASE WHEN 2 BETWEEN place_from AND place_from + tied_players - 1 THEN 1.0 / tied_players ELSE 0 END AS second_credit,
+          CASE WHEN 3 BETWEEN place_from AND place_from + tied_players - 1 THEN 1.0 / tied_players ELSE 0 END AS third_credit,
+          CASE WHEN 4 BETWEEN place_from AND place_from + tied_players - 1 THEN 1.0 / tied_players ELSE 0 END AS fourth_credit
+        FROM (
+          SELECT
+            gpr.*,
+            RANK() OVER (PARTITION BY gpr.game_id ORDER BY gpr.table_point DESC) AS place_from,
+            COUNT(*) OVER (PARTITION BY gpr.game_id, gpr.table_point) AS tied_players
+          FROM game_player_results gpr
+          JOIN games g ON g.id = gpr.game_id
+          JOIN sessions s ON s.id = g.session_id
+          JOIN tournaments t ON t.id = s.tournament_id
+          WHERE (date_from IS NULL OR t.date >= date_from)
+            AND (date_to IS NULL OR t.date <= date_to)
+            AND (tournament_ids IS NULL OR t.id = ANY (tournament_ids))
+            AND (series IS NULL OR t.sub_name = series)
+        ) placed
+      ) gpr ON p.id = gpr.player_id
+    GROUP BY
+      p.id,
+      p.name
+  ) placement
+  LEFT JOIN (
+    SELECT
+      seat.player_id,
+      COUNT(*) AS hand_count,
+      ROUND(100.0 * COUNT(*) FILTER (WHERE seat.won) / COUNT(*), 2)::FLOAT4 AS agari_rate,
+      ROUND(100.0 * COUNT(*) FILTER (WHERE seat.dealt_in) / COUNT(*), 2)::FLOAT4 AS houjuu_rate,
+      ROUND(100.0 * COUNT(*) FILTER (WHERE seat.riichi) / COUNT(*), 2)::FLOAT4 AS riichi_rate,
+      ROUND(100.0 * COUNT(*) FILTER (WHERE seat.called) / COUNT(*), 2)::FLOAT4 AS call_rate,
+      AVG(seat.own_change - seat.deposits_collected) FILTER (WHERE seat.won)::FLOAT4 AS avg_win_value,
+      AVG(-seat.own_change) FILTER (WHERE seat.dealt_in)::FLOAT4 AS avg_deal_in_value,
+      ROUND(100.0 * COUNT(*) FILTER (WHERE seat.tenpai) / NULLIF(COUNT(*) FILTER (WHERE seat.outcome = 'draw'), 0), 2)::FLOAT4 AS ryuukyoku_tenpai_rate,
+      ROUND(100.0 * COUNT(*) FILTER (WHERE seat.won AND seat.outcome = 'tsumo') / NULLIF(COUNT(*) FILTER (WHERE seat.won), 0), 2)::FLOAT4 AS tsumo_ratio
+    FROM (
+      SELECT
+        gpr.player_id,
+        h.outcome,
+        hs.han IS NOT NULL AS won,
+        COALESCE(hs.seat_wind = h.discarder_seat, FALSE) AS dealt_in,
+        hs.riichi,
+        hs.called,
+        hs.tenpai,
+        -- Score change without the player's own riichi deposit
+        hs.point_change + CASE WHEN hs.riichi THEN 1000 ELSE 0 END AS own_change,
+        -- Deposits on the table go to the winner first in turn after the
+        -- discarder, so a double ron pays them out once
+        CASE
+          WHEN hs.han IS NOT NULL
+          AND ROW_NUMBER() OVER (
+            PARTITION BY hs.hand_id, hs.han IS NULL
+            ORDER BY (array_position(ARRAY['east', 'south', 'west', 'north'], hs.seat_wind)
+              - COALESCE(array_position(ARRAY['east', 'south', 'west', 'north'], h.discarder_seat), 0) + 4) % 4
+          ) = 1 THEN 1000 * (h.riichi_sticks + COUNT(*) FILTER (WHERE hs.riichi) OVER (PARTITION BY hs.hand_id))
+          ELSE 0
+        END AS deposits_collected
+      FROM game_hand_seats hs
+      JOIN game_hands h ON h.id = hs.hand_id
+      JOIN game_player_results gpr ON gpr.game_id = h.game_id AND gpr.seat_wind = hs.seat_wind
+      JOIN games g ON g.id = h.game_id
+      JOIN sessions s ON s.id = g.session_id
+      JOIN tournaments t ON t.id = s.tournament_id
+      WHERE (date_from IS NULL OR t.date >= date_from)
+        AND (date_to IS NULL OR t.date <= date_to)
+        AND (tournament_ids IS NULL OR t.id = ANY (tournament_ids))
+        AND (series IS NULL OR t.sub_name = series)
+    ) seat
+    GROUP BY
+      seat.player_id
+  ) hands ON hands.player_id = placement.player_id;
+$$ LANGUAGE sql STABLE;
+
+CREATE VIEW player_stats AS
+SELECT * FROM player_stats_filtered (NULL, NULL, NULL, NULL);
@@ -0,0 +1,7 @@
+-- Whether 4 han 30 fu and 3 han 60 fu round up to a mangan. Hand logs are
+-- checked against the payments this gives.
+ALTER TABLE rulesets ADD COLUMN kiriage_mangan BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Number of yakuman for yakuman wins (2 for a double yakuman); han is then
+-- not used for the payment
+ALTER TABLE game_hand_seats ADD COLUMN yakuman INTEGER CHECK (yakuman > 0);
@@ -17,6 +17,10 @@ sqlx database create
 echo "Running migrations..."
 sqlx migrate run
 
+# Migrations leave player_ratings empty; rate every game once before serving
+echo "Rebuilding ratings..."
+"$1" rebuild-ratings
+
 echo "Database setup complete!"
 echo "========================"
 
@@ -0,0 +1,411 @@
+use argon2::{
+    password_hash::{rand_core::OsRng, rand_core::RngCore, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use axum::{
+    async_trait,
+    extract::{FromRef, FromRequestParts, State},
+    http::{header, request::Parts, StatusCode},
+};
+use chrono::{DateTime, Utc};
+use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgPool;
+use utoipa::ToSchema;
+
+use crate::config::AuthConfig;
+use crate::error::{AppError, Problem};
+use crate::extract::Json;
+use crate::validation::ValidationErrors;
+
+/// Account roles, ordered so that a higher role includes every lower one.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[repr(u8)]
+pub enum Role {
+    Viewer = 0,
+    Scorekeeper = 1,
+    Admin = 2,
+}
+
+impl Role {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Role::Viewer => "viewer",
+            Role::Scorekeeper => "scorekeeper",
+            Role::Admin => "admin",
+        }
+    }
+
+    fn from_db(value: &str) -> Option<Role> {
+        match value {
+            "viewer" => Some(Role::Viewer),
+            "scorekeeper" => Some(Role::Scorekeeper),
+            "admin" => Some(Role::Admin),
+            _ => None,
+        }
+    }
+}
+
+/// Key material for signing and checking session tokens.
+#[derive(Clone)]
+pub struct AuthKeys {
+    encoding: EncodingKey,
+    decoding: DecodingKey,
+    token_ttl: chrono::Duration,
+}
+
+impl AuthKeys {
+    pub fn new(secret: &[u8], token_ttl_hours: i64) -> Self {
+        Self {
+            encoding: EncodingKey::from_secret(secret),
+            decoding: DecodingKey::from_secret(secret),
+            token_ttl: chrono::Duration::hours(token_ttl_hours),
+        }
+    }
+
+    /// Uses the configured secret, falling back to a random one so a missing
+    /// setting never means a guessable key. Tokens then do not survive a
+    /// restart.
+    pub fn from_config(config: &AuthConfig) -> Self {
+        match config.secret.as_deref() {
+            Some(secret) => Self::new(secret.as_bytes(), config.token_ttl_hours),
+            None => {
+                tracing::warn!("auth secret is not set; using a random secret, sessions end on restart");
+                let mut secret = [0u8; 32];
+                OsRng.fill_bytes(&mut secret);
+                Self::new(&secret, config.token_ttl_hours)
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct Claims {
+    sub: i32,
+    username: String,
+    role: Role,
+    exp: i64,
+}
+
+/// The signed-in user, taken from a `Bearer` token.
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct AuthUser {
+    pub id: i32,
+    pub username: String,
+    pub role: Role,
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for AuthUser
+where
+    AuthKeys: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let keys = AuthKeys::from_ref(state);
+        let token = parts
+            .headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or(AppError::Unauthorized)?;
+
+        let claims = jsonwebtoken::decode::<Claims>(token, &keys.decoding, &Validation::default())
+            .map_err(|_| AppError::Unauthorized)?
+            .claims;
+
+        Ok(AuthUser {
+            id: claims.sub,
+            username: claims.username,
+            role: claims.role,
+        })
+    }
+}
+
+/// Extractor that only lets through users holding at least role `MIN`.
+pub struct RequireRole<const MIN: u8>(pub AuthUser);
+
+pub type Scorekeeper = RequireRole<{ Role::Scorekeeper as u8 }>;
+pub type Admin = RequireRole<{ Role::Admin as u8 }>;
+
+#[async_trait]
+impl<S, const MIN: u8> FromRequestParts<S> for RequireRole<MIN>
+where
+    AuthKeys: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let user = AuthUser::from_request_parts(parts, state).await?;
+        if (user.role as u8) < MIN {
+            return Err(AppError::Forbidden);
+        }
+        Ok(RequireRole(user))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct LoginRequest {
+    username: String,
+    password: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct LoginResponse {
+    token: String,
+    #[schema(value_type = String, format = DateTime)]
+    expires_at: DateTime<Utc>,
+    user: AuthUser,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct CreateUserRequest {
+    username: String,
+    password: String,
+    role: Role,
+}
+
+#[derive(sqlx::FromRow)]
+struct UserRow {
+    id: i32,
+    username: String,
+    password_hash: String,
+    role: String,
+}
+
+impl UserRow {
+    fn into_user(self) -> Option<AuthUser> {
+        Some(AuthUser {
+            id: self.id,
+            username: self.username,
+            role: Role::from_db(&self.role)?,
+        })
+    }
+}
+
+/// Hashes a password with argon2 off the async runtime.
+pub async fn hash_password(password: String) -> Result<String, AppError> {
+    tokio::task::spawn_blocking(move || {
+        let salt = SaltString::generate(&mut OsRng);
+        Argon2::default()
+            .hash_password(password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+    })
+    .await
+    .map_err(|e| AppError::internal(format!("password hashing task failed: {}", e)))?
+    .map_err(|e| AppError::internal(format!("password hashing failed: {}", e)))
+}
+
+/// Hash of a password no one uses, made with the default Argon2 parameters.
+/// Logins for unknown usernames are verified against it, so they take as long
+/// as a wrong password and do not reveal which usernames exist.
+const DUMMY_PASSWORD_HASH: &str =
+    "$argon2id$v=19$m=19456,t=2,p=1$H+oUPcfCHPA9H/kuvmxp6g$LHzvsVVFo654q/W7/tF1jQq7pK4pnKAziGR0PdLjgSw";
+
+async fn verify_password(password: String, password_hash: String) -> bool {
+    tokio::task::spawn_blocking(move || {
+        PasswordHash::new(&password_hash)
+            .map(|parsed| Argon2::default().verify_password(password.as_bytes(), &parsed).is_ok())
+            .unwrap_or(false)
+    })
+    .await
+    .unwrap_or(false)
+}
+
+fn issue_token(keys: &AuthKeys, user: &AuthUser) -> Result<(String, DateTime<Utc>), AppError> {
+    let expires_at = Utc::now() + keys.token_ttl;
+    let claims = Claims {
+        sub: user.id,
+        username: user.username.clone(),
+        role: user.role,
+        exp: expires_at.timestamp(),
+    };
+    let token = jsonwebtoken::encode(&Header::default(), &claims, &keys.encoding)
+        .map_err(|e| AppError::internal(format!("failed to sign token: {}", e)))?;
+    Ok((token, expires_at))
+}
+
+/// Creates the configured admin account when no admin exists yet, so a
+/// fresh database can be signed into.
+pub async fn bootstrap_admin(pool: &PgPool, config: &AuthConfig) -> Result<(), sqlx::Error> {
+    let (Some(username), Some(password)) = (config.admin_username.clone(), config.admin_password.clone()) else {
+        return Ok(());
+    };
+
+    let has_admin = sqlx::query_scalar::<_, bool>("SELECT EXISTS (SELECT 1 FROM users WHERE role = 'admin')")
+        .fetch_one(pool)
+        .await?;
+    if has_admin {
+        return Ok(());
+    }
+
+    let Ok(password_hash) = hash_password(password).await else {
+        tracing::error!("failed to hash the admin password; no admin created");
+        return Ok(());
+    };
+    sqlx::query("INSERT INTO users (username, password_hash, role) VALUES ($1, $2, 'admin')")
+        .bind(&username)
+        .bind(&password_hash)
+        .execute(pool)
+        .await?;
+    tracing::info!("created initial admin user {}", username);
+
+    Ok(())
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/auth/login",
+    request_body = LoginRequest,
+    responses(
+        (status = 200, description = "Signed in", body = LoginResponse),
+        (status = 400, description = "Malformed request body", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Unknown user or wrong password", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "Unexpected server error", body = Problem, content_type = "application/problem+json")
+    )
+)]
+pub async fn login(
+    State(pool): State<PgPool>,
+    State(keys): State<AuthKeys>,
+    Json(request): Json<LoginRequest>,
+) -> Result<Json<LoginResponse>, AppError> {
+    let row = sqlx::query_as::<_, UserRow>(
+        "SELECT id, username, password_hash, role FROM users WHERE username = $1"
+    )
+    .bind(&request.username)
+    .fetch_optional(&pool)
+    .await?;
+
+    let password_hash = row.as_ref().map_or(DUMMY_PASSWORD_HASH, |row| &row.password_hash).to_string();
+    let verified = verify_password(request.password, password_hash).await;
+    let row = row.filter(|_| verified).ok_or(AppError::Unauthorized)?;
+
+    let user = row
+        .into_user()
+        .ok_or_else(|| AppError::internal("user has an unknown role"))?;
+    let (token, expires_at) = issue_token(&keys, &user)?;
+
+    Ok(Json(LoginResponse {
+        token,
+        expires_at,
+        user,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/auth/me",
+    responses(
+        (status = 200, description = "The signed-in user", body = AuthUser),
+        (status = 401, description = "Missing or invalid token", body = Problem, content_type = "application/problem+json")
+    ),
+    security(("bearer" = []))
+)]
+pub async fn me(user: AuthUser) -> Json<AuthUser> {
+    Json(user)
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/users",
+    responses(
+        (status = 200, description = "User list", body = [AuthUser]),
+        (status = 401, description = "Missing or invalid token", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Admin role required", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "Unexpected server error", body = Problem, content_type = "application/problem+json")
+    ),
+    security(("bearer" = []))
+)]
+pub async fn get_users(
+    _: Admin,
+    State(pool): State<PgPool>,
+) -> Result<Json<Vec<AuthUser>>, AppError> {
+    let rows = sqlx::query_as::<_, UserRow>(
+        "SELECT id, username, password_hash, role FROM users ORDER BY id"
+    )
+    .fetch_all(&pool)
+    .await?;
+
+    Ok(Json(rows.into_iter().filter_map(UserRow::into_user).collect()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/users",
+    request_body = CreateUserRequest,
+    responses(
+        (status = 201, description = "User created", body = AuthUser),
+        (status = 400, description = "Malformed request body", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Missing or invalid token", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Admin role required", body = Problem, content_type = "application/problem+json"),
+        (status = 409, description = "Username already taken", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid username or password", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "Unexpected server error", body = Problem, content_type = "application/problem+json")
+    ),
+    security(("bearer" = []))
+)]
+pub async fn create_user(
+    _: Admin,
+    State(pool): State<PgPool>,
+    Json(request): Json<CreateUserRequest>,
+) -> Result<(StatusCode, Json<AuthUser>), AppError> {
+    let mut errors = ValidationErrors::default();
+    let username = request.username.trim().to_string();
+    if username.is_empty() {
+        errors.push("username", "empty_username", "username must not be empty");
+    }
+    if request.password.chars().count() < 8 {
+        errors.push("password", "password_too_short", "password must be at least 8 characters");
+    }
+    errors.into_result(())?;
+
+    let password_hash = hash_password(request.password).await?;
+
+    let id = sqlx::query_scalar::<_, i32>(
+        "INSERT INTO users (username, password_hash, role) VALUES ($1, $2, $3) RETURNING id"
+    )
+    .bind(&username)
+    .bind(&password_hash)
+    .bind(request.role.as_str())
+    .fetch_one(&pool)
+    .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(AuthUser {
+            id,
+            username,
+            role: request.role,
+        }),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dummy_hash_costs_as_much_as_a_real_one() {
+        let dummy = PasswordHash::new(DUMMY_PASSWORD_HASH).unwrap();
+        let params = argon2::Params::try_from(&dummy).unwrap();
+        let default = argon2::Params::default();
+        assert_eq!(dummy.algorithm, argon2::Algorithm::default().ident());
+        assert_eq!(
+            (params.m_cost(), params.t_cost(), params.p_cost()),
+            (default.m_cost(), default.t_cost(), default.p_cost())
+        );
+    }
+
+    #[tokio::test]
+    async fn passwords_verify_only_against_their_own_hash() {
+        let hash = hash_password("correct horse".to_string()).await.unwrap();
+        assert!(verify_password("correct horse".to_string(), hash.clone()).await);
+        assert!(!verify_password("battery staple".to_string(), hash).await);
+        assert!(!verify_password("correct horse".to_string(), DUMMY_PASSWORD_HASH.to_string()).await);
+        assert!(!verify_password("correct horse".to_string(), "not a hash".to_string()).await);
+    }
+}
@@ -0,0 +1,501 @@
+use std::{fmt, net::SocketAddr, path::PathBuf, time::Duration};
+
+use axum::http::HeaderValue;
+use serde::Deserialize;
+use sqlx::postgres::PgPoolOptions;
+
+use crate::db::RetryPolicy;
+
+/// Used when neither `CONFIG_FILE` nor `config.toml` in the working
+/// directory is present.
+const DEFAULT_CONFIG_FILE: &str = "config.toml";
+
+/// Server settings, read from an optional TOML file and then overridden by
+/// environment variables. See `config.example.toml` for every key.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub server: ServerConfig,
+    pub database: DatabaseConfig,
+    pub cors: CorsConfig,
+    pub logging: LoggingConfig,
+    pub openapi: OpenApiConfig,
+    pub auth: AuthConfig,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    pub listen_addr: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatabaseConfig {
+    pub url: Option<String>,
+    pub max_connections: u32,
+    pub acquire_timeout_ms: u64,
+    pub idle_timeout_ms: Option<u64>,
+    pub connect_attempts: u32,
+    pub connect_timeout_ms: u64,
+    pub connect_backoff_ms: u64,
+    pub connect_max_backoff_ms: u64,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CorsConfig {
+    /// Exact origins allowed to call the API; `["*"]` allows any origin.
+    pub allowed_origins: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LoggingConfig {
+    pub format: LogFormat,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OpenApiConfig {
+    /// Where to write the OpenAPI document at startup, which the frontend
+    /// generates its API types from; unset to skip.
+    pub file: Option<PathBuf>,
+    pub swagger_ui: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuthConfig {
+    pub secret: Option<String>,
+    pub token_ttl_hours: i64,
+    pub admin_username: Option<String>,
+    pub admin_password: Option<String>,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            listen_addr: "0.0.0.0:8000".to_string(),
+        }
+    }
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        let retry = RetryPolicy::default();
+        Self {
+            url: None,
+            max_connections: 10,
+            acquire_timeout_ms: 5000,
+            idle_timeout_ms: None,
+            connect_attempts: retry.max_attempts,
+            connect_timeout_ms: retry.attempt_timeout.as_millis() as u64,
+            connect_backoff_ms: retry.initial_backoff.as_millis() as u64,
+            connect_max_backoff_ms: retry.max_backoff.as_millis() as u64,
+        }
+    }
+}
+
+impl Default for OpenApiConfig {
+    fn default() -> Self {
+        Self {
+            file: Some(PathBuf::from("openapi.json")),
+            swagger_ui: false,
+        }
+    }
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self {
+            secret: None,
+            token_ttl_hours: 12,
+            admin_username: None,
+            admin_password: None,
+        }
+    }
+}
+
+/// A setting that could not be loaded or does not make sense.
+#[derive(Debug)]
+pub struct ConfigError(String);
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid configuration: {}", self.0)
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl Config {
+    /// Loads the file named by `CONFIG_FILE` (or `config.toml` if present),
+    /// applies environment overrides and validates the result.
+    pub fn load() -> Result<Self, ConfigError> {
+        let mut config = match std::env::var("CONFIG_FILE") {
+            Ok(path) => Self::from_file(&PathBuf::from(path))?,
+            Err(_) if PathBuf::from(DEFAULT_CONFIG_FILE).exists() => {
+                Self::from_file(&PathBuf::from(DEFAULT_CONFIG_FILE))?
+            }
+            Err(_) => Self::default(),
+        };
+        config.apply_env()?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn from_file(path: &PathBuf) -> Result<Self, ConfigError> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| ConfigError(format!("cannot read {}: {}", path.display(), e)))?;
+        toml::from_str(&contents).map_err(|e| ConfigError(format!("{}: {}", path.display(), e)))
+    }
+
+    fn apply_env(&mut self) -> Result<(), ConfigError> {
+        self.apply_overrides(&|name| std::env::var(name).ok())
+    }
+
+    /// Overrides file values with the variables `env` returns; empty values
+    /// count as unset, except for `OPENAPI_FILE`.
+    fn apply_overrides(&mut self, env: &Env) -> Result<(), ConfigError> {
+        if let Some(value) = env_string(env, "LISTEN_ADDR") {
+            self.server.listen_addr = value;
+        }
+        if let Some(value) = env_string(env, "DATABASE_URL") {
+            self.database.url = Some(value);
+        }
+        if let Some(value) = env_parse(env, "DB_MAX_CONNECTIONS")? {
+            self.database.max_connections = value;
+        }
+        if let Some(value) = env_parse(env, "DB_ACQUIRE_TIMEOUT_MS")? {
+            self.database.acquire_timeout_ms = value;
+        }
+        if let Some(value) = env_parse(env, "DB_IDLE_TIMEOUT_MS")? {
+            self.database.idle_timeout_ms = Some(value);
+        }
+        if let Some(value) = env_parse(env, "DB_CONNECT_ATTEMPTS")? {
+            self.database.connect_attempts = value;
+        }
+        if let Some(value) = env_parse(env, "DB_CONNECT_TIMEOUT_MS")? {
+            self.database.connect_timeout_ms = value;
+        }
+        if let Some(value) = env_parse(env, "DB_CONNECT_BACKOFF_MS")? {
+            self.database.connect_backoff_ms = value;
+        }
+        if let Some(value) = env_parse(env, "DB_CONNECT_MAX_BACKOFF_MS")? {
+            self.database.connect_max_backoff_ms = value;
+        }
+        if let Some(value) = env_string(env, "CORS_ALLOWED_ORIGINS") {
+            self.cors.allowed_origins = value
+                .split(',')
+                .map(|origin| origin.trim().to_string())
+                .filter(|origin| !origin.is_empty())
+                .collect();
+        }
+        if let Some(value) = env_string(env, "LOG_FORMAT") {
+            self.logging.format = match value.as_str() {
+                "text" => LogFormat::Text,
+                "json" => LogFormat::Json,
+                other => {
+                    return Err(ConfigError(format!(
+                        "LOG_FORMAT must be \"text\" or \"json\", got {:?}",
+                        other
+                    )))
+                }
+            };
+        }
+        if let Some(value) = env("OPENAPI_FILE") {
+            // An empty value turns the file off
+            self.openapi.file = (!value.is_empty()).then(|| PathBuf::from(value));
+        }
+        if let Some(value) = env_parse(env, "SWAGGER_UI")? {
+            self.openapi.swagger_ui = value;
+        }
+        if let Some(value) = env_string(env, "AUTH_SECRET") {
+            self.auth.secret = Some(value);
+        }
+        if let Some(value) = env_parse(env, "AUTH_TOKEN_TTL_HOURS")? {
+            self.auth.token_ttl_hours = value;
+        }
+        if let Some(value) = env_string(env, "ADMIN_USERNAME") {
+            self.auth.admin_username = Some(value);
+        }
+        if let Some(value) = env_string(env, "ADMIN_PASSWORD") {
+            self.auth.admin_password = Some(value);
+        }
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), ConfigError> {
+        self.listen_addr()?;
+
+        match &self.database.url {
+            None => {
+                return Err(ConfigError(
+                    "database.url is required (set DATABASE_URL or database.url in the config file)".to_string(),
+                ))
+            }
+            Some(url) if !url.starts_with("postgres://") && !url.starts_with("postgresql://") => {
+                return Err(ConfigError("database.url must start with postgres:// or postgresql://".to_string()))
+            }
+            Some(_) => {}
+        }
+        if self.database.max_connections == 0 {
+            return Err(ConfigError("database.max_connections must be at least 1".to_string()));
+        }
+        if self.database.acquire_timeout_ms == 0 {
+            return Err(ConfigError("database.acquire_timeout_ms must be greater than 0".to_string()));
+        }
+        if self.database.connect_attempts == 0 {
+            return Err(ConfigError("database.connect_attempts must be at least 1".to_string()));
+        }
+        if self.database.connect_timeout_ms == 0 {
+            return Err(ConfigError("database.connect_timeout_ms must be greater than 0".to_string()));
+        }
+        if self.database.connect_max_backoff_ms < self.database.connect_backoff_ms {
+            return Err(ConfigError(
+                "database.connect_max_backoff_ms must not be less than database.connect_backoff_ms".to_string(),
+            ));
+        }
+
+        if self.cors.allowed_origins.iter().any(|origin| origin == "*") {
+            if self.cors.allowed_origins.len() > 1 {
+                return Err(ConfigError("cors.allowed_origins cannot mix \"*\" with explicit origins".to_string()));
+            }
+        } else {
+            for origin in &self.cors.allowed_origins {
+                let valid_scheme = origin.starts_with("http://") || origin.starts_with("https://");
+                if !valid_scheme || origin.ends_with('/') || HeaderValue::from_str(origin).is_err() {
+                    return Err(ConfigError(format!(
+                        "cors.allowed_origins entry {:?} must look like https://example.com (scheme and host, no trailing slash)",
+                        origin
+                    )));
+                }
+            }
+        }
+
+        if self.openapi.swagger_ui && !cfg!(feature = "swagger-ui") {
+            return Err(ConfigError(
+                "openapi.swagger_ui needs a server built with the swagger-ui cargo feature".to_string(),
+            ));
+        }
+
+        if self.auth.token_ttl_hours <= 0 {
+            return Err(ConfigError("auth.token_ttl_hours must be greater than 0".to_string()));
+        }
+        if self.auth.admin_username.is_some() != self.auth.admin_password.is_some() {
+            return Err(ConfigError(
+                "auth.admin_username and auth.admin_password must be set together".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn listen_addr(&self) -> Result<SocketAddr, ConfigError> {
+        self.server.listen_addr.parse().map_err(|_| {
+            ConfigError(format!(
+                "server.listen_addr {:?} is not a socket address like 0.0.0.0:8000",
+                self.server.listen_addr
+            ))
+        })
+    }
+
+    pub fn database_url(&self) -> &str {
+        self.database.url.as_deref().unwrap_or_default()
+    }
+
+    pub fn pool_options(&self) -> PgPoolOptions {
+        PgPoolOptions::new()
+            .max_connections(self.database.max_connections)
+            .acquire_timeout(Duration::from_millis(self.database.acquire_timeout_ms))
+            .idle_timeout(self.database.idle_timeout_ms.map(Duration::from_millis))
+    }
+
+    pub fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: self.database.connect_attempts,
+            attempt_timeout: Duration::from_millis(self.database.connect_timeout_ms),
+            initial_backoff: Duration::from_millis(self.database.connect_backoff_ms),
+            max_backoff: Duration::from_millis(self.database.connect_max_backoff_ms),
+        }
+    }
+}
+
+/// Looks up an environment variable by name.
+type Env = dyn Fn(&str) -> Option<String>;
+
+fn env_string(env: &Env, name: &str) -> Option<String> {
+    env(name).filter(|value| !value.is_empty())
+}
+
+fn env_parse<T: std::str::FromStr>(env: &Env, name: &str) -> Result<Option<T>, ConfigError> {
+    match env_string(env, name) {
+        Some(value) => value
+            .parse()
+            .map(Some)
+            .map_err(|_| ConfigError(format!("{} has an invalid value {:?}", name, value))),
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn valid() -> Config {
+        let mut config = Config::default();
+        config.database.url = Some("postgres://localhost/majiang".to_string());
+        config
+    }
+
+    fn message(config: &Config) -> String {
+        config.validate().unwrap_err().to_string()
+    }
+
+    fn overridden(file: &str, vars: &[(&str, &str)]) -> Result<Config, ConfigError> {
+        let vars: Vec<(String, String)> = vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        let mut config: Config = toml::from_str(file).unwrap();
+        config.apply_overrides(&move |name| vars.iter().find(|(k, _)| k == name).map(|(_, v)| v.clone()))?;
+        Ok(config)
+    }
+
+    #[test]
+    fn defaults_with_a_database_url_are_valid() {
+        assert!(valid().validate().is_ok());
+    }
+
+    #[test]
+    fn bad_listen_address_is_rejected() {
+        for addr in ["0.0.0.0", "0.0.0.0:http", "localhost:8000", "0.0.0.0:70000"] {
+            let mut config = valid();
+            config.server.listen_addr = addr.to_string();
+            assert!(message(&config).contains("server.listen_addr"), "{} was accepted", addr);
+        }
+    }
+
+    #[test]
+    fn missing_or_empty_database_url_is_rejected() {
+        let mut config = valid();
+        config.database.url = None;
+        assert!(message(&config).contains("database.url is required"));
+
+        config.database.url = Some(String::new());
+        assert!(message(&config).contains("must start with postgres://"));
+
+        config.database.url = Some("mysql://localhost/majiang".to_string());
+        assert!(message(&config).contains("must start with postgres://"));
+    }
+
+    #[test]
+    fn empty_database_url_in_the_environment_leaves_it_unset() {
+        let config = overridden("", &[("DATABASE_URL", "")]).unwrap();
+        assert!(message(&config).contains("database.url is required"));
+    }
+
+    #[test]
+    fn zero_pool_size_is_rejected() {
+        let mut config = valid();
+        config.database.max_connections = 0;
+        assert!(message(&config).contains("database.max_connections must be at least 1"));
+    }
+
+    #[test]
+    fn backoff_cap_below_the_initial_backoff_is_rejected() {
+        let mut config = valid();
+        config.database.connect_backoff_ms = 2000;
+        config.database.connect_max_backoff_ms = 1000;
+        assert!(message(&config).contains("connect_max_backoff_ms"));
+    }
+
+    #[test]
+    fn wildcard_origin_cannot_be_mixed_with_others() {
+        let mut config = valid();
+        config.cors.allowed_origins = vec!["*".to_string(), "https://example.com".to_string()];
+        assert!(message(&config).contains("cannot mix"));
+
+        config.cors.allowed_origins = vec!["https://example.com/".to_string()];
+        assert!(message(&config).contains("no trailing slash"));
+    }
+
+    #[test]
+    fn admin_credentials_must_be_set_together() {
+        let mut config = valid();
+        config.auth.admin_username = Some("admin".to_string());
+        assert!(message(&config).contains("must be set together"));
+    }
+
+    const FILE: &str = r#"
+        [server]
+        listen_addr = "127.0.0.1:9000"
+
+        [database]
+        url = "postgres://file/majiang"
+        max_connections = 4
+
+        [cors]
+        allowed_origins = ["https://file.example"]
+
+        [openapi]
+        file = "spec.json"
+    "#;
+
+    #[test]
+    fn environment_overrides_file_values() {
+        let config = overridden(
+            FILE,
+            &[
+                ("LISTEN_ADDR", "0.0.0.0:8080"),
+                ("DATABASE_URL", "postgres://env/majiang"),
+                ("DB_MAX_CONNECTIONS", "20"),
+                ("CORS_ALLOWED_ORIGINS", "https://a.example, https://b.example,"),
+                ("LOG_FORMAT", "json"),
+                ("AUTH_TOKEN_TTL_HOURS", "2"),
+            ],
+        )
+        .unwrap();
+        assert_eq!(config.server.listen_addr, "0.0.0.0:8080");
+        assert_eq!(config.database_url(), "postgres://env/majiang");
+        assert_eq!(config.database.max_connections, 20);
+        assert_eq!(config.cors.allowed_origins, ["https://a.example", "https://b.example"]);
+        assert_eq!(config.logging.format, LogFormat::Json);
+        assert_eq!(config.auth.token_ttl_hours, 2);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn file_values_stay_when_the_environment_is_unset_or_empty() {
+        let config = overridden(FILE, &[("LISTEN_ADDR", ""), ("DB_MAX_CONNECTIONS", "")]).unwrap();
+        assert_eq!(config.server.listen_addr, "127.0.0.1:9000");
+        assert_eq!(config.database_url(), "postgres://file/majiang");
+        assert_eq!(config.database.max_connections, 4);
+        assert_eq!(config.openapi.file, Some(PathBuf::from("spec.json")));
+    }
+
+    #[test]
+    fn empty_openapi_file_turns_the_file_off() {
+        let config = overridden(FILE, &[("OPENAPI_FILE", "")]).unwrap();
+        assert_eq!(config.openapi.file, None);
+    }
+
+    #[test]
+    fn unparsable_environment_values_are_rejected() {
+        let error = overridden(FILE, &[("DB_MAX_CONNECTIONS", "many")]).unwrap_err();
+        assert!(error.to_string().contains("DB_MAX_CONNECTIONS has an invalid value"));
+        let error = overridden(FILE, &[("LOG_FORMAT", "xml")]).unwrap_err();
+        assert!(error.to_string().contains("LOG_FORMAT"));
+    }
+
+    #[test]
+    fn unknown_file_keys_are_rejected() {
+        assert!(toml::from_str::<Config>("[server]\nport = 8000").is_err());
+    }
+}
@@ -0,0 +1,60 @@
+use std::time::Duration;
+
+use sqlx::postgres::{PgPool, PgPoolOptions};
+
+/// How hard startup tries to reach Postgres before giving up.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    /// How long a single attempt may take before it counts as failed
+    pub attempt_timeout: Duration,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 10,
+            attempt_timeout: Duration::from_secs(5),
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Connects to Postgres, retrying with exponential backoff so the server can
+/// start before the database is reachable.
+pub async fn connect_with_retry(
+    database_url: &str,
+    options: PgPoolOptions,
+    policy: &RetryPolicy,
+) -> Result<PgPool, sqlx::Error> {
+    let mut backoff = policy.initial_backoff;
+    let mut attempt = 1;
+    loop {
+        let connect = tokio::time::timeout(policy.attempt_timeout, options.clone().connect(database_url));
+        let result = connect.await.unwrap_or(Err(sqlx::Error::PoolTimedOut));
+        match result {
+            Ok(pool) => return Ok(pool),
+            Err(error) if attempt < policy.max_attempts => {
+                tracing::warn!(
+                    "database connection attempt {}/{} failed: {}; retrying in {:?}",
+                    attempt,
+                    policy.max_attempts,
+                    error,
+                    backoff
+                );
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(policy.max_backoff);
+                attempt += 1;
+            }
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+/// Round-trips a trivial query to confirm the pool can reach the database.
+pub async fn ping(pool: &PgPool) -> Result<(), sqlx::Error> {
+    sqlx::query("SELECT 1").execute(pool).await.map(|_| ())
+}
@@ -0,0 +1,229 @@
+use axum::{
+    extract::rejection::{JsonRejection, PathRejection, QueryRejection},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::validation::{ValidationErrors, Violation};
+
+pub const PROBLEM_CONTENT_TYPE: &str = "application/problem+json";
+
+/// RFC 7807 problem details body returned for every error response.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct Problem {
+    /// URI reference identifying the problem type
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    pub title: String,
+    pub status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Machine-readable error code, e.g. `not_found` or `validation_failed`
+    pub code: String,
+    /// Individual violations, present for `validation_failed`
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub violations: Vec<Violation>,
+}
+
+#[derive(Debug)]
+pub enum AppError {
+    Validation(ValidationErrors),
+    BadRequest { code: &'static str, detail: String },
+    /// A request axum could not extract, keeping the status it chose
+    Rejected { status: StatusCode, code: &'static str, detail: String },
+    Unauthorized,
+    Forbidden,
+    NotFound(String),
+    Conflict { code: &'static str, detail: String },
+    UnprocessableReference(String),
+    ServiceUnavailable(String),
+    Database(sqlx::Error),
+    Internal(String),
+}
+
+impl AppError {
+    pub fn not_found(what: impl Into<String>) -> Self {
+        AppError::NotFound(format!("{} not found", what.into()))
+    }
+
+    pub fn conflict(code: &'static str, detail: impl Into<String>) -> Self {
+        AppError::Conflict {
+            code,
+            detail: detail.into(),
+        }
+    }
+
+    pub fn bad_request(code: &'static str, detail: impl Into<String>) -> Self {
+        AppError::BadRequest {
+            code,
+            detail: detail.into(),
+        }
+    }
+
+    pub fn internal(detail: impl Into<String>) -> Self {
+        AppError::Internal(detail.into())
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            AppError::Validation(_) | AppError::UnprocessableReference(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            AppError::BadRequest { .. } => StatusCode::BAD_REQUEST,
+            AppError::Rejected { status, .. } => *status,
+            AppError::Unauthorized => StatusCode::UNAUTHORIZED,
+            AppError::Forbidden => StatusCode::FORBIDDEN,
+            AppError::NotFound(_) => StatusCode::NOT_FOUND,
+            AppError::Conflict { .. } => StatusCode::CONFLICT,
+            AppError::ServiceUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            AppError::Database(_) | AppError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::Validation(_) => "validation_failed",
+            AppError::BadRequest { code, .. } | AppError::Rejected { code, .. } | AppError::Conflict { code, .. } => code,
+            AppError::Unauthorized => "unauthorized",
+            AppError::Forbidden => "forbidden",
+            AppError::NotFound(_) => "not_found",
+            AppError::UnprocessableReference(_) => "unknown_reference",
+            AppError::ServiceUnavailable(_) => "not_ready",
+            AppError::Database(_) => "database_error",
+            AppError::Internal(_) => "internal_error",
+        }
+    }
+
+    fn detail(&self) -> Option<String> {
+        match self {
+            AppError::Validation(errors) => Some(format!("{} violation(s)", errors.violations.len())),
+            AppError::BadRequest { detail, .. }
+            | AppError::Rejected { detail, .. }
+            | AppError::Conflict { detail, .. }
+            | AppError::NotFound(detail)
+            | AppError::UnprocessableReference(detail) => Some(detail.clone()),
+            AppError::Unauthorized => Some("missing or invalid bearer token".to_string()),
+            AppError::Forbidden => Some("your role does not allow this action".to_string()),
+            AppError::ServiceUnavailable(_) => Some("service is not ready".to_string()),
+            // Never leak database or internal details to clients; they are logged instead
+            AppError::Database(_) | AppError::Internal(_) => None,
+        }
+    }
+}
+
+impl std::fmt::Display for AppError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AppError::Database(error) => write!(f, "database error: {}", error),
+            AppError::Internal(detail) => write!(f, "internal error: {}", detail),
+            AppError::ServiceUnavailable(detail) => write!(f, "not ready: {}", detail),
+            other => write!(f, "{}: {}", other.code(), other.detail().unwrap_or_default()),
+        }
+    }
+}
+
+impl From<sqlx::Error> for AppError {
+    fn from(error: sqlx::Error) -> Self {
+        if let sqlx::Error::RowNotFound = error {
+            return AppError::NotFound("resource not found".to_string());
+        }
+        if let Some(db_error) = error.as_database_error() {
+            let constraint = db_error.constraint().unwrap_or("unknown").to_string();
+            if db_error.is_foreign_key_violation() {
+                return AppError::UnprocessableReference(describe_foreign_key(&constraint));
+            }
+            if db_error.is_unique_violation() {
+                return AppError::conflict("duplicate", format!("value already exists ({})", constraint));
+            }
+            if db_error.is_check_violation() {
+                return AppError::bad_request("constraint_violation", format!("value rejected by {}", constraint));
+            }
+        }
+        AppError::Database(error)
+    }
+}
+
+/// Names the request field behind a foreign-key violation where we know it.
+fn describe_foreign_key(constraint: &str) -> String {
+    match constraint {
+        "sessions_tournament_id_fkey" => "tournament_id does not refer to an existing tournament".to_string(),
+        "game_player_results_player_id_fkey" => "player_id does not refer to an existing player".to_string(),
+        "games_session_id_fkey" => "session does not exist".to_string(),
+        "tournaments_ruleset_id_fkey" => "ruleset_id does not refer to an existing ruleset".to_string(),
+        "season_tournaments_tournament_id_fkey" => "tournament_ids refers to a tournament that does not exist".to_string(),
+        other => format!("referenced row does not exist or is still in use ({})", other),
+    }
+}
+
+impl From<serde_json::Error> for AppError {
+    fn from(error: serde_json::Error) -> Self {
+        AppError::Internal(format!("failed to decode stored JSON: {}", error))
+    }
+}
+
+impl From<JsonRejection> for AppError {
+    fn from(rejection: JsonRejection) -> Self {
+        match rejection {
+            JsonRejection::JsonSyntaxError(_) => AppError::bad_request("malformed_json", rejection.body_text()),
+            JsonRejection::JsonDataError(_) => AppError::bad_request("invalid_body", rejection.body_text()),
+            JsonRejection::MissingJsonContentType(_) => AppError::Rejected {
+                status: rejection.status(),
+                code: "unsupported_media_type",
+                detail: rejection.body_text(),
+            },
+            _ => AppError::Rejected {
+                status: rejection.status(),
+                code: "unreadable_body",
+                detail: rejection.body_text(),
+            },
+        }
+    }
+}
+
+impl From<QueryRejection> for AppError {
+    fn from(rejection: QueryRejection) -> Self {
+        AppError::bad_request("invalid_query", rejection.body_text())
+    }
+}
+
+impl From<PathRejection> for AppError {
+    fn from(rejection: PathRejection) -> Self {
+        match rejection {
+            PathRejection::FailedToDeserializePathParams(_) => AppError::bad_request("invalid_path", rejection.body_text()),
+            // A route whose path has no parameters to extract
+            _ => AppError::internal(rejection.body_text()),
+        }
+    }
+}
+
+impl From<ValidationErrors> for AppError {
+    fn from(errors: ValidationErrors) -> Self {
+        AppError::Validation(errors)
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        let status = self.status();
+        if status.is_server_error() {
+            tracing::error!(status = status.as_u16(), "{}", self);
+        } else {
+            tracing::warn!(status = status.as_u16(), "{}", self);
+        }
+
+        let problem = Problem {
+            problem_type: "about:blank".to_string(),
+            title: status.canonical_reason().unwrap_or("Error").to_string(),
+            status: status.as_u16(),
+            detail: self.detail(),
+            code: self.code().to_string(),
+            violations: match self {
+                AppError::Validation(errors) => errors.violations,
+                _ => Vec::new(),
+            },
+        };
+
+        (status, [(header::CONTENT_TYPE, PROBLEM_CONTENT_TYPE)], Json(problem)).into_response()
+    }
+}
@@ -0,0 +1,165 @@
+use axum::{
+    async_trait,
+    extract::{FromRequest, FromRequestParts, Request},
+    http::request::Parts,
+    response::{IntoResponse, Response},
+};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::error::AppError;
+
+/// `axum::Json` whose rejections are problem responses. Also usable as a
+/// response body.
+#[derive(Debug)]
+pub struct Json<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for Json<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let axum::Json(value) = axum::Json::<T>::from_request(req, state).await?;
+        Ok(Json(value))
+    }
+}
+
+impl<T: Serialize> IntoResponse for Json<T> {
+    fn into_response(self) -> Response {
+        axum::Json(self.0).into_response()
+    }
+}
+
+/// `axum::extract::Query` whose rejections are problem responses.
+#[derive(Debug)]
+pub struct Query<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for Query<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let axum::extract::Query(value) = axum::extract::Query::<T>::from_request_parts(parts, state).await?;
+        Ok(Query(value))
+    }
+}
+
+/// `axum::extract::Path` whose rejections are problem responses.
+#[derive(Debug)]
+pub struct Path<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for Path<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let axum::extract::Path(value) = axum::extract::Path::<T>::from_request_parts(parts, state).await?;
+        Ok(Path(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        body::{to_bytes, Body},
+        http::{header, Request, StatusCode},
+        routing::{get, post},
+        Router,
+    };
+    use serde::Deserialize;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::error::{Problem, PROBLEM_CONTENT_TYPE};
+
+    #[derive(Deserialize, Serialize)]
+    struct Counter {
+        count: i32,
+    }
+
+    #[derive(Deserialize)]
+    struct Params {
+        limit: i64,
+    }
+
+    fn router() -> Router {
+        Router::new()
+            .route("/body", post(|Json(body): Json<Counter>| async move { Json(body) }))
+            .route("/query", get(|Query(params): Query<Params>| async move { params.limit.to_string() }))
+            .route("/items/:id", get(|Path(id): Path<i32>| async move { id.to_string() }))
+    }
+
+    async fn send(request: Request<Body>) -> (StatusCode, Option<String>, Problem) {
+        let response = router().oneshot(request).await.unwrap();
+        let status = response.status();
+        let content_type = response.headers().get(header::CONTENT_TYPE).map(|v| v.to_str().unwrap().to_string());
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, content_type, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    fn post_json(body: &'static str) -> Request<Body> {
+        Request::post("/body").header(header::CONTENT_TYPE, "application/json").body(Body::from(body)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn malformed_json_is_a_problem() {
+        let (status, content_type, problem) = send(post_json("{\"count\":")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(content_type.as_deref(), Some(PROBLEM_CONTENT_TYPE));
+        assert_eq!(problem.code, "malformed_json");
+        assert_eq!(problem.status, 400);
+    }
+
+    #[tokio::test]
+    async fn json_of_the_wrong_shape_is_a_problem() {
+        let (status, _, problem) = send(post_json("{\"count\":\"three\"}")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(problem.code, "invalid_body");
+    }
+
+    #[tokio::test]
+    async fn body_without_json_content_type_keeps_its_status() {
+        let request = Request::post("/body").body(Body::from("{\"count\":3}")).unwrap();
+        let (status, content_type, problem) = send(request).await;
+        assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(content_type.as_deref(), Some(PROBLEM_CONTENT_TYPE));
+        assert_eq!(problem.code, "unsupported_media_type");
+    }
+
+    #[tokio::test]
+    async fn bad_query_is_a_problem() {
+        let request = Request::get("/query?limit=many").body(Body::empty()).unwrap();
+        let (status, content_type, problem) = send(request).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(content_type.as_deref(), Some(PROBLEM_CONTENT_TYPE));
+        assert_eq!(problem.code, "invalid_query");
+    }
+
+    #[tokio::test]
+    async fn bad_path_is_a_problem() {
+        let request = Request::get("/items/seven").body(Body::empty()).unwrap();
+        let (status, content_type, problem) = send(request).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(content_type.as_deref(), Some(PROBLEM_CONTENT_TYPE));
+        assert_eq!(problem.code, "invalid_path");
+    }
+
+    #[tokio::test]
+    async fn valid_requests_pass_through() {
+        let response = router().oneshot(post_json("{\"count\":3}")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&bytes[..], b"{\"count\":3}");
+    }
+}
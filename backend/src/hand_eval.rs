@@ -0,0 +1,1245 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use utoipa::ToSchema;
+
+use crate::error::{AppError, Problem};
+use crate::extract::Json;
+use crate::hand_score::{self, HandValue, Payments, WinType};
+use crate::validation::ValidationErrors;
+use crate::SeatWind;
+
+/// Distinct tiles: nine of each suit and seven honors.
+const TILE_KINDS: usize = 34;
+/// Tiles in a winning hand, counting each kan as three.
+const HAND_SIZE: usize = 14;
+const COPIES_PER_TILE: usize = 4;
+/// Han a yakuman is listed with; its value comes from the yakuman count.
+const YAKUMAN_HAN: i32 = 13;
+/// Suit letters of the notation, honors last.
+const SUITS: [char; 4] = ['m', 'p', 's', 'z'];
+
+/// A tile: 0-8 are characters (m), 9-17 circles (p), 18-26 bamboo (s), then
+/// East, South, West, North, white, green and red dragon (1z-7z).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Tile(u8);
+
+impl Tile {
+    /// 0-2 for the suits, 3 for honors
+    fn suit(self) -> usize {
+        (self.0 / 9) as usize
+    }
+
+    fn number(self) -> u8 {
+        self.0 % 9 + 1
+    }
+
+    fn is_honor(self) -> bool {
+        self.0 >= 27
+    }
+
+    fn is_terminal(self) -> bool {
+        !self.is_honor() && matches!(self.number(), 1 | 9)
+    }
+
+    fn is_terminal_or_honor(self) -> bool {
+        self.is_honor() || self.is_terminal()
+    }
+
+    fn is_dragon(self) -> bool {
+        self.0 >= 31
+    }
+
+    fn wind(self) -> Option<SeatWind> {
+        (27..31).contains(&self.0).then(|| SeatWind::ALL[(self.0 - 27) as usize])
+    }
+
+    /// 2-3-4-6-8 bamboo and the green dragon
+    fn is_green(self) -> bool {
+        matches!(self.0, 19 | 20 | 21 | 23 | 25 | 32)
+    }
+
+    /// The dora this tile indicates
+    fn next(self) -> Tile {
+        match self.0 {
+            27..=30 => Tile(27 + (self.0 - 26) % 4),
+            31..=33 => Tile(31 + (self.0 - 30) % 3),
+            _ => Tile(self.0 / 9 * 9 + (self.0 % 9 + 1) % 9),
+        }
+    }
+
+    /// The tile `offset` places up the same suit
+    fn plus(self, offset: u8) -> Tile {
+        Tile(self.0 + offset)
+    }
+}
+
+/// Parses tile notation such as `123m456p789s11z`: digits followed by their
+/// suit, with `0` for a red five. Returns the tiles and how many red fives
+/// there are in each of the m, p and s suits; a suit has only one.
+pub fn parse_tiles(notation: &str) -> Result<(Vec<Tile>, [i32; 3]), String> {
+    let mut tiles = Vec::new();
+    let mut red_fives = [0; 3];
+    let mut pending = Vec::new();
+    for c in notation.chars().filter(|c| !c.is_whitespace()) {
+        if let Some(digit) = c.to_digit(10) {
+            pending.push(digit as u8);
+            continue;
+        }
+        let suit = SUITS.iter().position(|&suit| suit == c).ok_or_else(|| format!("'{}' is not a suit", c))?;
+        if pending.is_empty() {
+            return Err(format!("suit '{}' has no tiles before it", c));
+        }
+        for digit in pending.drain(..) {
+            let number = match (digit, suit) {
+                (0, 0..=2) => {
+                    red_fives[suit] += 1;
+                    if red_fives[suit] > 1 {
+                        return Err(format!("there is only one red five in {}", c));
+                    }
+                    5
+                }
+                (1..=9, 0..=2) | (1..=7, 3) => digit,
+                _ => return Err(format!("{}{} is not a tile", digit, c)),
+            };
+            tiles.push(Tile(suit as u8 * 9 + number - 1));
+        }
+    }
+    if !pending.is_empty() {
+        return Err("the last tiles have no suit".to_string());
+    }
+    Ok((tiles, red_fives))
+}
+
+/// Writes sorted tiles in notation, e.g. `123m11z`.
+fn notation(tiles: &[Tile]) -> String {
+    let mut out = String::new();
+    for (index, tile) in tiles.iter().enumerate() {
+        out.push(char::from(b'0' + tile.number()));
+        if tiles.get(index + 1).is_none_or(|next| next.suit() != tile.suit()) {
+            out.push(SUITS[tile.suit()]);
+        }
+    }
+    out
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MeldKind {
+    Chi,
+    Pon,
+    Kan,
+    ClosedKan,
+}
+
+/// A called or declared meld, tiles sorted.
+#[derive(Debug, Clone)]
+pub struct Meld {
+    pub kind: MeldKind,
+    pub tiles: Vec<Tile>,
+}
+
+impl Meld {
+    fn block(&self) -> Block {
+        let kind = match self.kind {
+            MeldKind::Chi => GroupKind::Sequence,
+            MeldKind::Pon => GroupKind::Triplet,
+            MeldKind::Kan | MeldKind::ClosedKan => GroupKind::Quad,
+        };
+        Block {
+            kind,
+            first: self.tiles[0],
+            called: self.kind != MeldKind::ClosedKan,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupKind {
+    Pair,
+    Sequence,
+    Triplet,
+    Quad,
+    /// The thirteen terminals and honors
+    Orphans,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HandForm {
+    /// Four sets and a pair
+    Standard,
+    SevenPairs,
+    ThirteenOrphans,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Wait {
+    /// Two-sided sequence wait
+    Ryanmen,
+    /// Middle of a sequence
+    Kanchan,
+    /// 3 after 12, or 7 before 89
+    Penchan,
+    /// One of two pairs
+    Shanpon,
+    /// Pair wait
+    Tanki,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Yaku {
+    Riichi,
+    DoubleRiichi,
+    Ippatsu,
+    MenzenTsumo,
+    Pinfu,
+    Tanyao,
+    Iipeikou,
+    Haitei,
+    Houtei,
+    RinshanKaihou,
+    Chankan,
+    Haku,
+    Hatsu,
+    Chun,
+    /// Triplet of the seat wind
+    Jikaze,
+    /// Triplet of the round wind
+    Bakaze,
+    SanshokuDoujun,
+    Ittsu,
+    Chanta,
+    Chiitoitsu,
+    Toitoi,
+    Sanankou,
+    SanshokuDoukou,
+    Sankantsu,
+    Shousangen,
+    Honroutou,
+    Junchan,
+    Ryanpeikou,
+    Honitsu,
+    Chinitsu,
+    KokushiMusou,
+    Suuankou,
+    Daisangen,
+    Shousuushii,
+    Daisuushii,
+    Tsuuiisou,
+    Chinroutou,
+    Ryuuiisou,
+    ChuurenPoutou,
+    Suukantsu,
+    Tenhou,
+    Chiihou,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct YakuHan {
+    pub yaku: Yaku,
+    pub han: i32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct Group {
+    pub kind: GroupKind,
+    pub tiles: String,
+    /// Called from another player's discard
+    pub open: bool,
+    /// Completed by the winning tile
+    pub winning: bool,
+}
+
+/// One way of reading the hand, scored.
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct Interpretation {
+    pub form: HandForm,
+    pub groups: Vec<Group>,
+    pub wait: Wait,
+    pub yaku: Vec<YakuHan>,
+    pub dora: i32,
+    pub ura_dora: i32,
+    pub aka_dora: i32,
+    /// Yaku and dora; 0 without a yaku, as such a hand cannot win
+    pub han: i32,
+    /// Absent for thirteen orphans
+    pub fu: Option<i32>,
+    /// Absent without a yaku
+    pub value: Option<HandValue>,
+    pub payments: Option<Payments>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct Evaluation {
+    /// Whether the tiles form a winning shape, with or without a yaku
+    pub complete: bool,
+    pub interpretations: Vec<Interpretation>,
+    /// The highest-scoring interpretation that has a yaku
+    pub best: Option<Interpretation>,
+}
+
+/// Circumstances of a win that the tiles alone do not show.
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct Situation {
+    pub win: WinType,
+    pub seat_wind: SeatWind,
+    pub round_wind: SeatWind,
+    #[serde(default)]
+    pub riichi: bool,
+    #[serde(default)]
+    pub double_riichi: bool,
+    #[serde(default)]
+    pub ippatsu: bool,
+    /// Won on the last tile of the wall (haitei) or its discard (houtei)
+    #[serde(default)]
+    pub last_tile: bool,
+    /// Won on the replacement tile after a kan
+    #[serde(default)]
+    pub rinshan: bool,
+    /// Won on a tile added to another player's kan
+    #[serde(default)]
+    pub chankan: bool,
+    #[serde(default)]
+    pub tenhou: bool,
+    #[serde(default)]
+    pub chiihou: bool,
+    #[serde(default)]
+    pub honba: i32,
+    /// Riichi deposits the winner collects from the table
+    #[serde(default)]
+    pub riichi_sticks: i32,
+    /// Round 4 han 30 fu and 3 han 60 fu up to a mangan
+    #[serde(default)]
+    pub kiriage_mangan: bool,
+}
+
+impl Situation {
+    fn any_riichi(&self) -> bool {
+        self.riichi || self.double_riichi
+    }
+
+    fn dealer(&self) -> bool {
+        self.seat_wind == SeatWind::East
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct MeldRequest {
+    pub kind: MeldKind,
+    /// e.g. `555z`, or `406p` with a red five
+    pub tiles: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct EvaluateRequest {
+    /// Concealed tiles, winning tile included, e.g. `123m456p789s1122z`
+    pub hand: String,
+    #[serde(default)]
+    pub melds: Vec<MeldRequest>,
+    /// One tile of `hand`, e.g. `2z`
+    pub winning_tile: String,
+    #[serde(default)]
+    pub dora_indicators: String,
+    /// Only counted with riichi
+    #[serde(default)]
+    pub ura_dora_indicators: String,
+    #[serde(flatten)]
+    #[schema(inline)]
+    pub situation: Situation,
+}
+
+/// A hand ready to evaluate; see `EvaluateRequest::parse`.
+#[derive(Debug, Clone)]
+pub struct HandInput {
+    /// Winning tile included
+    pub concealed: Vec<Tile>,
+    pub melds: Vec<Meld>,
+    pub winning_tile: Tile,
+    pub dora_indicators: Vec<Tile>,
+    pub ura_dora_indicators: Vec<Tile>,
+    pub red_fives: i32,
+    pub situation: Situation,
+}
+
+impl HandInput {
+    fn closed(&self) -> bool {
+        self.melds.iter().all(|meld| meld.kind == MeldKind::ClosedKan)
+    }
+
+    fn all_tiles(&self) -> Vec<Tile> {
+        let mut tiles = self.concealed.clone();
+        tiles.extend(self.melds.iter().flat_map(|meld| meld.tiles.iter().copied()));
+        tiles
+    }
+}
+
+fn parse_field(notation: &str, field: &str, errors: &mut ValidationErrors) -> (Vec<Tile>, [i32; 3]) {
+    parse_tiles(notation).unwrap_or_else(|message| {
+        errors.push(field, "invalid_tiles", message);
+        (Vec::new(), [0; 3])
+    })
+}
+
+fn add_red_fives(total: &mut [i32; 3], red_fives: [i32; 3]) {
+    for (total, count) in total.iter_mut().zip(red_fives) {
+        *total += count;
+    }
+}
+
+fn validate_meld(kind: MeldKind, tiles: &[Tile]) -> bool {
+    let same = tiles.windows(2).all(|pair| pair[0] == pair[1]);
+    match kind {
+        MeldKind::Chi => {
+            tiles.len() == 3
+                && !tiles[0].is_honor()
+                && tiles[0].number() <= 7
+                && tiles[1] == tiles[0].plus(1)
+                && tiles[2] == tiles[0].plus(2)
+        }
+        MeldKind::Pon => tiles.len() == 3 && same,
+        MeldKind::Kan | MeldKind::ClosedKan => tiles.len() == 4 && same,
+    }
+}
+
+impl EvaluateRequest {
+    /// Parses the notation and checks that tiles and situation fit together.
+    pub fn parse(&self) -> Result<HandInput, ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+        let (mut concealed, mut red_fives) = parse_field(&self.hand, "hand", &mut errors);
+        concealed.sort();
+
+        let mut melds = Vec::new();
+        for (index, meld) in self.melds.iter().enumerate() {
+            let field = format!("melds[{}].tiles", index);
+            let (mut tiles, red) = parse_field(&meld.tiles, &field, &mut errors);
+            tiles.sort();
+            add_red_fives(&mut red_fives, red);
+            if tiles.is_empty() {
+                continue;
+            }
+            if !validate_meld(meld.kind, &tiles) {
+                errors.push(field, "invalid_meld", format!("{} is not a valid meld of its kind", meld.tiles));
+                continue;
+            }
+            melds.push(Meld { kind: meld.kind, tiles });
+        }
+
+        let winning_tile = match parse_tiles(&self.winning_tile) {
+            Ok((tiles, _)) if tiles.len() == 1 => Some(tiles[0]),
+            Ok(_) => {
+                errors.push("winning_tile", "invalid_tiles", "winning_tile must be a single tile");
+                None
+            }
+            Err(message) => {
+                errors.push("winning_tile", "invalid_tiles", message);
+                None
+            }
+        };
+        if winning_tile.is_some_and(|tile| !concealed.contains(&tile)) {
+            errors.push("winning_tile", "not_in_hand", "winning_tile must be one of the hand's tiles");
+        }
+        let (dora_indicators, dora_red) = parse_field(&self.dora_indicators, "dora_indicators", &mut errors);
+        let (ura_dora_indicators, ura_red) =
+            parse_field(&self.ura_dora_indicators, "ura_dora_indicators", &mut errors);
+        // Indicators are turned over from the wall, so they are tiles of the
+        // same set as the hand's
+        let mut red_in_play = red_fives;
+        add_red_fives(&mut red_in_play, dora_red);
+        add_red_fives(&mut red_in_play, ura_red);
+
+        if errors.is_empty() {
+            if concealed.len() + 3 * self.melds.len() != HAND_SIZE {
+                errors.push(
+                    "hand",
+                    "tile_count",
+                    format!("hand and melds must hold {} tiles, counting each kan as 3", HAND_SIZE),
+                );
+            }
+            let mut counts = [0; TILE_KINDS];
+            let in_play = concealed
+                .iter()
+                .chain(melds.iter().flat_map(|meld| meld.tiles.iter()))
+                .chain(&dora_indicators)
+                .chain(&ura_dora_indicators);
+            for tile in in_play {
+                counts[tile.0 as usize] += 1;
+            }
+            if counts.iter().any(|&count| count > COPIES_PER_TILE) {
+                errors.push(
+                    "hand",
+                    "too_many_copies",
+                    format!("no tile can appear more than {} times, counting dora indicators", COPIES_PER_TILE),
+                );
+            }
+            if red_in_play.iter().any(|&count| count > 1) {
+                errors.push("hand", "too_many_red_fives", "there is at most one red five per suit");
+            }
+        }
+        validate_situation(&self.situation, &melds, &ura_dora_indicators, &mut errors);
+        let winning_tile = match winning_tile {
+            Some(tile) if errors.is_empty() => tile,
+            _ => return Err(errors),
+        };
+
+        Ok(HandInput {
+            concealed,
+            melds,
+            winning_tile,
+            dora_indicators,
+            ura_dora_indicators,
+            red_fives: red_fives.iter().sum(),
+            situation: self.situation.clone(),
+        })
+    }
+}
+
+fn validate_situation(situation: &Situation, melds: &[Meld], ura_dora: &[Tile], errors: &mut ValidationErrors) {
+    let closed = melds.iter().all(|meld| meld.kind == MeldKind::ClosedKan);
+    let tsumo = situation.win == WinType::Tsumo;
+    if situation.any_riichi() && !closed {
+        errors.push("riichi", "open_hand", "riichi needs a closed hand");
+    }
+    if situation.ippatsu && !situation.any_riichi() {
+        errors.push("ippatsu", "without_riichi", "ippatsu needs riichi");
+    }
+    if !ura_dora.is_empty() && !situation.any_riichi() {
+        errors.push("ura_dora_indicators", "without_riichi", "ura dora only count with riichi");
+    }
+    if situation.rinshan && (!tsumo || !melds.iter().any(|meld| meld.tiles.len() == COPIES_PER_TILE)) {
+        errors.push("rinshan", "inconsistent", "rinshan needs a tsumo and a kan");
+    }
+    if situation.chankan && tsumo {
+        errors.push("chankan", "inconsistent", "chankan is won by ron");
+    }
+    if situation.tenhou && (!tsumo || !situation.dealer() || !melds.is_empty()) {
+        errors.push("tenhou", "inconsistent", "tenhou is the dealer's tsumo on the first draw");
+    }
+    if situation.chiihou && (!tsumo || situation.dealer() || !melds.is_empty()) {
+        errors.push("chiihou", "inconsistent", "chiihou is a non-dealer's tsumo on the first draw");
+    }
+    if situation.honba < 0 {
+        errors.push("honba", "negative", "honba must not be negative");
+    }
+    if situation.riichi_sticks < 0 {
+        errors.push("riichi_sticks", "negative", "riichi_sticks must not be negative");
+    }
+}
+
+/// A pair or set; `first` is its lowest tile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Block {
+    kind: GroupKind,
+    first: Tile,
+    called: bool,
+}
+
+impl Block {
+    fn new(kind: GroupKind, first: Tile) -> Block {
+        Block {
+            kind,
+            first,
+            called: false,
+        }
+    }
+
+    fn tiles(&self) -> Vec<Tile> {
+        match self.kind {
+            GroupKind::Sequence => vec![self.first, self.first.plus(1), self.first.plus(2)],
+            GroupKind::Pair => vec![self.first; 2],
+            GroupKind::Triplet => vec![self.first; 3],
+            GroupKind::Quad | GroupKind::Orphans => vec![self.first; 4],
+        }
+    }
+
+    fn is_triplet(&self) -> bool {
+        matches!(self.kind, GroupKind::Triplet | GroupKind::Quad)
+    }
+
+    fn has_terminal_or_honor(&self) -> bool {
+        match self.kind {
+            GroupKind::Sequence => matches!(self.first.number(), 1 | 7),
+            _ => self.first.is_terminal_or_honor(),
+        }
+    }
+}
+
+/// A decomposition of the hand with the block the winning tile completed.
+struct Shape {
+    form: HandForm,
+    /// Concealed blocks, then melds; empty for thirteen orphans
+    blocks: Vec<Block>,
+    winning: Option<usize>,
+    wait: Wait,
+}
+
+fn counts_of(tiles: &[Tile]) -> [usize; TILE_KINDS] {
+    let mut counts = [0; TILE_KINDS];
+    for tile in tiles {
+        counts[tile.0 as usize] += 1;
+    }
+    counts
+}
+
+/// Splits the counted tiles into triplets and sequences in every possible way.
+fn decompose_sets(counts: &mut [usize; TILE_KINDS], current: &mut Vec<Block>, out: &mut Vec<Vec<Block>>) {
+    let Some(index) = counts.iter().position(|&count| count > 0) else {
+        out.push(current.clone());
+        return;
+    };
+    let tile = Tile(index as u8);
+    if counts[index] >= 3 {
+        counts[index] -= 3;
+        current.push(Block::new(GroupKind::Triplet, tile));
+        decompose_sets(counts, current, out);
+        current.pop();
+        counts[index] += 3;
+    }
+    if !tile.is_honor() && tile.number() <= 7 && counts[index + 1] > 0 && counts[index + 2] > 0 {
+        for offset in 0..3 {
+            counts[index + offset] -= 1;
+        }
+        current.push(Block::new(GroupKind::Sequence, tile));
+        decompose_sets(counts, current, out);
+        current.pop();
+        for offset in 0..3 {
+            counts[index + offset] += 1;
+        }
+    }
+}
+
+fn wait_of(block: &Block, winning_tile: Tile) -> Wait {
+    match block.kind {
+        GroupKind::Pair => Wait::Tanki,
+        GroupKind::Sequence if winning_tile == block.first.plus(1) => Wait::Kanchan,
+        GroupKind::Sequence if winning_tile == block.first && block.first.number() == 7 => Wait::Penchan,
+        GroupKind::Sequence if winning_tile == block.first.plus(2) && block.first.number() == 1 => Wait::Penchan,
+        GroupKind::Sequence => Wait::Ryanmen,
+        _ => Wait::Shanpon,
+    }
+}
+
+fn find_shapes(input: &HandInput) -> Vec<Shape> {
+    let mut shapes = Vec::new();
+    let mut counts = counts_of(&input.concealed);
+    let meld_blocks: Vec<Block> = input.melds.iter().map(Meld::block).collect();
+
+    let mut seen = HashSet::new();
+    for pair in 0..TILE_KINDS {
+        if counts[pair] < 2 {
+            continue;
+        }
+        counts[pair] -= 2;
+        let mut decompositions = Vec::new();
+        decompose_sets(&mut counts, &mut Vec::new(), &mut decompositions);
+        counts[pair] += 2;
+
+        for sets in decompositions {
+            let mut blocks = vec![Block::new(GroupKind::Pair, Tile(pair as u8))];
+            blocks.extend(sets);
+            for winning in 0..blocks.len() {
+                let block = blocks[winning];
+                if !block.tiles().contains(&input.winning_tile) {
+                    continue;
+                }
+                let wait = wait_of(&block, input.winning_tile);
+                // Identical sets give identical readings
+                if !seen.insert((blocks.clone(), block, wait)) {
+                    continue;
+                }
+                let mut all_blocks = blocks.clone();
+                all_blocks.extend(meld_blocks.iter().copied());
+                shapes.push(Shape {
+                    form: HandForm::Standard,
+                    blocks: all_blocks,
+                    winning: Some(winning),
+                    wait,
+                });
+            }
+        }
+    }
+
+    if input.melds.is_empty() {
+        if counts.iter().all(|&count| count == 0 || count == 2) {
+            let blocks: Vec<Block> = (0..TILE_KINDS)
+                .filter(|&index| counts[index] == 2)
+                .map(|index| Block::new(GroupKind::Pair, Tile(index as u8)))
+                .collect();
+            let winning = blocks.iter().position(|block| block.first == input.winning_tile);
+            shapes.push(Shape {
+                form: HandForm::SevenPairs,
+                blocks,
+                winning,
+                wait: Wait::Tanki,
+            });
+        }
+        let orphans = (0..TILE_KINDS).filter(|&index| Tile(index as u8).is_terminal_or_honor());
+        if orphans.clone().all(|index| counts[index] >= 1) && orphans.map(|index| counts[index]).sum::<usize>() == HAND_SIZE {
+            shapes.push(Shape {
+                form: HandForm::ThirteenOrphans,
+                blocks: Vec::new(),
+                winning: None,
+                wait: Wait::Tanki,
+            });
+        }
+    }
+    shapes
+}
+
+/// A shape read together with the situation, for yaku and fu.
+struct Reading<'a> {
+    input: &'a HandInput,
+    shape: &'a Shape,
+    tiles: Vec<Tile>,
+    closed: bool,
+}
+
+impl Reading<'_> {
+    fn ron(&self) -> bool {
+        self.input.situation.win == WinType::Ron
+    }
+
+    fn pair(&self) -> Option<&Block> {
+        match self.shape.form {
+            HandForm::Standard => self.shape.blocks.iter().find(|block| block.kind == GroupKind::Pair),
+            _ => None,
+        }
+    }
+
+    fn sets(&self) -> impl Iterator<Item = &Block> {
+        self.shape.blocks.iter().filter(|block| block.kind != GroupKind::Pair)
+    }
+
+    /// Whether the player drew every tile of the block; a set completed by
+    /// ron counts as open
+    fn is_concealed(&self, index: usize) -> bool {
+        !(self.shape.blocks[index].called || self.ron() && self.shape.winning == Some(index))
+    }
+
+    fn concealed_triplets(&self) -> usize {
+        (self.shape.blocks.iter().enumerate())
+            .filter(|&(index, block)| block.is_triplet() && self.is_concealed(index))
+            .count()
+    }
+
+    fn is_value_tile(&self, tile: Tile) -> bool {
+        let situation = &self.input.situation;
+        tile.is_dragon() || tile.wind() == Some(situation.seat_wind) || tile.wind() == Some(situation.round_wind)
+    }
+
+    fn single_suit(&self) -> Option<bool> {
+        let suits: HashSet<usize> = self.tiles.iter().filter(|tile| !tile.is_honor()).map(|tile| tile.suit()).collect();
+        (suits.len() == 1).then(|| self.tiles.iter().any(|tile| tile.is_honor()))
+    }
+
+    fn pinfu(&self) -> bool {
+        self.closed
+            && self.shape.form == HandForm::Standard
+            && self.shape.wait == Wait::Ryanmen
+            && self.sets().all(|block| block.kind == GroupKind::Sequence)
+            && self.pair().is_some_and(|pair| !self.is_value_tile(pair.first))
+    }
+
+    fn yakuman(&self) -> Vec<Yaku> {
+        let situation = &self.input.situation;
+        let mut yakuman = Vec::new();
+        if situation.tenhou {
+            yakuman.push(Yaku::Tenhou);
+        }
+        if situation.chiihou {
+            yakuman.push(Yaku::Chiihou);
+        }
+        if self.shape.form == HandForm::ThirteenOrphans {
+            yakuman.push(Yaku::KokushiMusou);
+        }
+        if self.tiles.iter().all(|tile| tile.is_honor()) {
+            yakuman.push(Yaku::Tsuuiisou);
+        }
+        if self.shape.form != HandForm::Standard {
+            return yakuman;
+        }
+
+        let triplets: Vec<&Block> = self.sets().filter(|block| block.is_triplet()).collect();
+        let dragons = triplets.iter().filter(|block| block.first.is_dragon()).count();
+        let winds = triplets.iter().filter(|block| block.first.wind().is_some()).count();
+        if self.concealed_triplets() == 4 {
+            yakuman.push(Yaku::Suuankou);
+        }
+        if dragons == 3 {
+            yakuman.push(Yaku::Daisangen);
+        }
+        if winds == 4 {
+            yakuman.push(Yaku::Daisuushii);
+        } else if winds == 3 && self.pair().is_some_and(|pair| pair.first.wind().is_some()) {
+            yakuman.push(Yaku::Shousuushii);
+        }
+        if self.tiles.iter().all(|tile| tile.is_terminal()) {
+            yakuman.push(Yaku::Chinroutou);
+        }
+        if self.tiles.iter().all(|tile| tile.is_green()) {
+            yakuman.push(Yaku::Ryuuiisou);
+        }
+        if triplets.iter().filter(|block| block.kind == GroupKind::Quad).count() == 4 {
+            yakuman.push(Yaku::Suukantsu);
+        }
+        if self.input.melds.is_empty() && self.single_suit() == Some(false) {
+            let counts = counts_of(&self.tiles);
+            let first = self.tiles[0].suit() * 9;
+            let minimum = [3, 1, 1, 1, 1, 1, 1, 1, 3];
+            if (0..9).all(|number| counts[first + number] >= minimum[number]) {
+                yakuman.push(Yaku::ChuurenPoutou);
+            }
+        }
+        yakuman
+    }
+
+    fn yaku(&self) -> Vec<YakuHan> {
+        let situation = &self.input.situation;
+        let closed = self.closed;
+        // Han of yaku worth one less when the hand is open
+        let reduced = |han: i32| if closed { han } else { han - 1 };
+        let mut yaku = Vec::new();
+        let mut add = |name, han| yaku.push(YakuHan { yaku: name, han });
+
+        if situation.double_riichi {
+            add(Yaku::DoubleRiichi, 2);
+        } else if situation.riichi {
+            add(Yaku::Riichi, 1);
+        }
+        if situation.ippatsu {
+            add(Yaku::Ippatsu, 1);
+        }
+        if closed && !self.ron() {
+            add(Yaku::MenzenTsumo, 1);
+        }
+        if situation.last_tile {
+            add(if self.ron() { Yaku::Houtei } else { Yaku::Haitei }, 1);
+        }
+        if situation.rinshan {
+            add(Yaku::RinshanKaihou, 1);
+        }
+        if situation.chankan {
+            add(Yaku::Chankan, 1);
+        }
+        if self.tiles.iter().all(|tile| !tile.is_terminal_or_honor()) {
+            add(Yaku::Tanyao, 1);
+        }
+        if self.tiles.iter().all(|tile| tile.is_terminal_or_honor()) {
+            add(Yaku::Honroutou, 2);
+        }
+        match self.single_suit() {
+            Some(true) => add(Yaku::Honitsu, reduced(3)),
+            Some(false) => add(Yaku::Chinitsu, reduced(6)),
+            None => {}
+        }
+        if self.shape.form == HandForm::SevenPairs {
+            add(Yaku::Chiitoitsu, 2);
+            return yaku;
+        }
+
+        let sets: Vec<&Block> = self.sets().collect();
+        let mut sequences: Vec<Tile> =
+            sets.iter().filter(|block| block.kind == GroupKind::Sequence).map(|block| block.first).collect();
+        sequences.sort();
+        let triplets: Vec<Tile> = sets.iter().filter(|block| block.is_triplet()).map(|block| block.first).collect();
+        let has_sequence = |first: Tile| sequences.contains(&first);
+        let has_triplet = |first: Tile| triplets.contains(&first);
+
+        if self.pinfu() {
+            add(Yaku::Pinfu, 1);
+        }
+        if closed {
+            let mut repeats = 0;
+            let mut index = 0;
+            while index + 1 < sequences.len() {
+                if sequences[index] == sequences[index + 1] {
+                    repeats += 1;
+                    index += 2;
+                } else {
+                    index += 1;
+                }
+            }
+            match repeats {
+                2 => add(Yaku::Ryanpeikou, 3),
+                1 => add(Yaku::Iipeikou, 1),
+                _ => {}
+            }
+        }
+        for &tile in &triplets {
+            match tile.0 {
+                31 => add(Yaku::Haku, 1),
+                32 => add(Yaku::Hatsu, 1),
+                33 => add(Yaku::Chun, 1),
+                _ => {}
+            }
+            if tile.wind() == Some(situation.seat_wind) {
+                add(Yaku::Jikaze, 1);
+            }
+            if tile.wind() == Some(situation.round_wind) {
+                add(Yaku::Bakaze, 1);
+            }
+        }
+        if (0..9).any(|number| (0..3).all(|suit| has_sequence(Tile(suit * 9 + number)))) {
+            add(Yaku::SanshokuDoujun, reduced(2));
+        }
+        if (0..3).any(|suit| [0, 3, 6].iter().all(|&number| has_sequence(Tile(suit * 9 + number)))) {
+            add(Yaku::Ittsu, reduced(2));
+        }
+        if (0..9).any(|number| (0..3).all(|suit| has_triplet(Tile(suit * 9 + number)))) {
+            add(Yaku::SanshokuDoukou, 2);
+        }
+        if triplets.len() == 4 {
+            add(Yaku::Toitoi, 2);
+        }
+        if self.concealed_triplets() == 3 {
+            add(Yaku::Sanankou, 2);
+        }
+        if sets.iter().filter(|block| block.kind == GroupKind::Quad).count() == 3 {
+            add(Yaku::Sankantsu, 2);
+        }
+        if triplets.iter().filter(|tile| tile.is_dragon()).count() == 2 && self.pair().is_some_and(|pair| pair.first.is_dragon()) {
+            add(Yaku::Shousangen, 2);
+        }
+        if !sequences.is_empty() && self.shape.blocks.iter().all(Block::has_terminal_or_honor) {
+            if self.tiles.iter().any(|tile| tile.is_honor()) {
+                add(Yaku::Chanta, reduced(2));
+            } else {
+                add(Yaku::Junchan, reduced(3));
+            }
+        }
+        yaku
+    }
+
+    fn fu(&self) -> Option<i32> {
+        let situation = &self.input.situation;
+        let ron = self.ron();
+        match self.shape.form {
+            HandForm::ThirteenOrphans => return None,
+            HandForm::SevenPairs => return Some(25),
+            HandForm::Standard => {}
+        }
+        if self.pinfu() && !ron {
+            return Some(20);
+        }
+
+        let mut fu = 20;
+        if self.closed && ron {
+            fu += 10;
+        }
+        if !ron {
+            fu += 2;
+        }
+        for (index, block) in self.shape.blocks.iter().enumerate() {
+            if block.is_triplet() {
+                let mut set_fu = 2;
+                if block.first.is_terminal_or_honor() {
+                    set_fu *= 2;
+                }
+                if self.is_concealed(index) {
+                    set_fu *= 2;
+                }
+                if block.kind == GroupKind::Quad {
+                    set_fu *= 4;
+                }
+                fu += set_fu;
+            }
+        }
+        if let Some(pair) = self.pair() {
+            if pair.first.is_dragon() {
+                fu += 2;
+            }
+            if pair.first.wind() == Some(situation.seat_wind) {
+                fu += 2;
+            }
+            if pair.first.wind() == Some(situation.round_wind) {
+                fu += 2;
+            }
+        }
+        if matches!(self.shape.wait, Wait::Kanchan | Wait::Penchan | Wait::Tanki) {
+            fu += 2;
+        }
+        // An open hand without fu still scores 30
+        if !self.closed && fu == 20 {
+            fu = 30;
+        }
+        Some(hand_score::round_fu(fu))
+    }
+
+    fn groups(&self) -> Vec<Group> {
+        if self.shape.form == HandForm::ThirteenOrphans {
+            return vec![Group {
+                kind: GroupKind::Orphans,
+                tiles: notation(&self.input.concealed),
+                open: false,
+                winning: true,
+            }];
+        }
+        (self.shape.blocks.iter().enumerate())
+            .map(|(index, block)| Group {
+                kind: block.kind,
+                tiles: notation(&block.tiles()),
+                open: block.called,
+                winning: self.shape.winning == Some(index),
+            })
+            .collect()
+    }
+}
+
+fn count_dora(tiles: &[Tile], indicators: &[Tile]) -> i32 {
+    indicators
+        .iter()
+        .map(|indicator| tiles.iter().filter(|&&tile| tile == indicator.next()).count() as i32)
+        .sum()
+}
+
+fn interpret(input: &HandInput, shape: &Shape) -> Interpretation {
+    let situation = &input.situation;
+    let reading = Reading {
+        input,
+        shape,
+        tiles: input.all_tiles(),
+        closed: input.closed(),
+    };
+    let dora = count_dora(&reading.tiles, &input.dora_indicators);
+    let ura_dora = if situation.any_riichi() { count_dora(&reading.tiles, &input.ura_dora_indicators) } else { 0 };
+    let aka_dora = input.red_fives;
+    let fu = reading.fu();
+
+    let yakuman = reading.yakuman();
+    let (yaku, han, value) = if yakuman.is_empty() {
+        let yaku = reading.yaku();
+        if yaku.is_empty() {
+            (yaku, 0, None)
+        } else {
+            let han = yaku.iter().map(|yaku| yaku.han).sum::<i32>() + dora + ura_dora + aka_dora;
+            (yaku, han, hand_score::hand_value(han, fu, None, situation.kiriage_mangan))
+        }
+    } else {
+        let count = yakuman.len() as i32;
+        let yaku = yakuman.into_iter().map(|yaku| YakuHan { yaku, han: YAKUMAN_HAN }).collect();
+        (yaku, YAKUMAN_HAN * count, hand_score::hand_value(YAKUMAN_HAN * count, fu, Some(count), situation.kiriage_mangan))
+    };
+    let payments = value.as_ref().map(|value| {
+        hand_score::payments(value, situation.dealer(), situation.win, situation.honba, situation.riichi_sticks)
+    });
+
+    Interpretation {
+        form: shape.form,
+        groups: reading.groups(),
+        wait: shape.wait,
+        yaku,
+        dora,
+        ura_dora,
+        aka_dora,
+        han,
+        fu,
+        value,
+        payments,
+    }
+}
+
+/// Finds every way to read a parsed hand as a win and scores each one.
+pub fn evaluate(input: &HandInput) -> Evaluation {
+    let interpretations: Vec<Interpretation> = find_shapes(input).iter().map(|shape| interpret(input, shape)).collect();
+    let best = (interpretations.iter())
+        .filter_map(|interpretation| interpretation.value.as_ref().map(|value| (value, interpretation)))
+        .max_by_key(|(value, interpretation)| (value.base_points, interpretation.han, interpretation.fu))
+        .map(|(_, interpretation)| interpretation.clone());
+
+    Evaluation {
+        complete: !interpretations.is_empty(),
+        interpretations,
+        best,
+    }
+}
+
+/// Reads a hand from tile notation and reports its yaku, han, fu and value,
+/// to settle disputed hands at the table.
+#[utoipa::path(
+    post,
+    path = "/v1/tools/evaluate",
+    request_body = EvaluateRequest,
+    responses(
+        (status = 200, description = "Every reading of the hand and the best one", body = Evaluation),
+        (status = 400, description = "Malformed request body", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid tiles, melds or situation", body = Problem, content_type = "application/problem+json")
+    )
+)]
+pub async fn evaluate_hand(Json(request): Json<EvaluateRequest>) -> Result<Json<Evaluation>, AppError> {
+    let input = request.parse()?;
+    Ok(Json(evaluate(&input)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hand_score::Limit;
+    use serde_json::{json, Value};
+
+    fn tile(notation: &str) -> Tile {
+        parse_tiles(notation).unwrap().0[0]
+    }
+
+    /// A South seat win in the East round.
+    fn request(hand: &str, melds: Value, winning_tile: &str, win: &str, riichi: bool) -> EvaluateRequest {
+        serde_json::from_value(json!({
+            "hand": hand,
+            "melds": melds,
+            "winning_tile": winning_tile,
+            "win": win,
+            "seat_wind": "south",
+            "round_wind": "east",
+            "riichi": riichi,
+        }))
+        .unwrap()
+    }
+
+    fn evaluate_request(request: EvaluateRequest) -> Evaluation {
+        evaluate(&request.parse().unwrap())
+    }
+
+    fn yaku_of(interpretation: &Interpretation) -> Vec<Yaku> {
+        interpretation.yaku.iter().map(|yaku| yaku.yaku).collect()
+    }
+
+    #[test]
+    fn fu_of_the_best_reading() {
+        let no_melds = json!([]);
+        let chi = json!([{ "kind": "chi", "tiles": "678p" }]);
+        let open_kan = json!([{ "kind": "kan", "tiles": "7777z" }]);
+        let closed_kan = json!([{ "kind": "closed_kan", "tiles": "7777z" }]);
+        let cases = [
+            // Pinfu: 20 fu on a tsumo, 30 with the closed ron bonus
+            ("234m567m345p678s88p", &no_melds, "4m", "tsumo", false, 20),
+            ("234m567m345p678s88p", &no_melds, "4m", "ron", false, 30),
+            // Kanchan, penchan and tanki each add 2, rounding 32 up to 40
+            ("234m567m345p678s88p", &no_melds, "3m", "ron", true, 40),
+            ("123m567m345p678s88p", &no_melds, "3m", "ron", true, 40),
+            ("234m567m345p678s88p", &no_melds, "8p", "ron", true, 40),
+            // A terminal or honor kan: 16 fu open, 32 concealed
+            ("234m567p345s88s", &open_kan, "4m", "ron", false, 40),
+            ("234m567p345s88s", &closed_kan, "4m", "ron", false, 70),
+            // An open hand without fu still scores 30
+            ("234m567p345s88s", &chi, "4m", "ron", false, 30),
+        ];
+        for (hand, melds, winning_tile, win, riichi, fu) in cases {
+            let evaluation = evaluate_request(request(hand, melds.clone(), winning_tile, win, riichi));
+            let best = evaluation.best.unwrap();
+            assert_eq!(best.fu, Some(fu), "{} {:?} on {} by {}", hand, melds, winning_tile, win);
+        }
+    }
+
+    #[test]
+    fn pinfu_needs_a_two_sided_wait() {
+        let ryanmen = evaluate_request(request("234m567m345p678s88p", json!([]), "4m", "ron", false));
+        assert_eq!(yaku_of(&ryanmen.best.unwrap()), [Yaku::Tanyao, Yaku::Pinfu]);
+        let kanchan = evaluate_request(request("234m567m345p678s88p", json!([]), "3m", "ron", true));
+        assert_eq!(yaku_of(&kanchan.best.unwrap()), [Yaku::Riichi, Yaku::Tanyao]);
+    }
+
+    #[test]
+    fn ryanpeikou_outscores_the_seven_pairs_reading() {
+        let evaluation = evaluate_request(request("223344m556677p88s", json!([]), "8s", "ron", false));
+        let forms: Vec<HandForm> = evaluation.interpretations.iter().map(|reading| reading.form).collect();
+        assert!(forms.contains(&HandForm::SevenPairs));
+
+        let best = evaluation.best.unwrap();
+        assert_eq!(best.form, HandForm::Standard);
+        assert_eq!(yaku_of(&best), [Yaku::Tanyao, Yaku::Ryanpeikou]);
+        assert_eq!(best.value.unwrap().limit, Some(Limit::Mangan));
+    }
+
+    #[test]
+    fn a_triplet_completed_by_ron_is_not_concealed() {
+        let tsumo = evaluate_request(request("222m444p666s345m88s", json!([]), "6s", "tsumo", false));
+        assert!(yaku_of(&tsumo.best.unwrap()).contains(&Yaku::Sanankou));
+
+        let ron = evaluate_request(request("222m444p666s345m88s", json!([]), "6s", "ron", false));
+        let best = ron.best.unwrap();
+        assert_eq!(best.wait, Wait::Shanpon);
+        assert_eq!(yaku_of(&best), [Yaku::Tanyao]);
+    }
+
+    #[test]
+    fn yakuman_hands() {
+        let kokushi = evaluate_request(request("19m19p19s12345677z", json!([]), "7z", "ron", false)).best.unwrap();
+        assert_eq!((kokushi.form, kokushi.fu), (HandForm::ThirteenOrphans, None));
+        assert_eq!(yaku_of(&kokushi), [Yaku::KokushiMusou]);
+        assert_eq!(kokushi.payments.unwrap().ron, Some(32000));
+
+        let chuuren = evaluate_request(request("11123455678999m", json!([]), "5m", "ron", false)).best.unwrap();
+        assert_eq!(yaku_of(&chuuren), [Yaku::ChuurenPoutou]);
+        assert_eq!(chuuren.value.unwrap().limit, Some(Limit::Yakuman));
+    }
+
+    #[test]
+    fn dora_indicators_wrap_within_their_suit_winds_and_dragons() {
+        let cases = [("9m", "1m"), ("5p", "6p"), ("9s", "1s"), ("4z", "1z"), ("1z", "2z"), ("7z", "5z"), ("5z", "6z")];
+        for (indicator, dora) in cases {
+            assert_eq!(tile(indicator).next(), tile(dora), "indicator {}", indicator);
+        }
+        assert_eq!(count_dora(&parse_tiles("111m55z").unwrap().0, &[tile("9m"), tile("7z")]), 5);
+    }
+
+    #[test]
+    fn a_complete_hand_without_yaku_has_no_best_reading() {
+        let chi = json!([{ "kind": "chi", "tiles": "123p" }]);
+        let evaluation = evaluate_request(request("234m567p345s88s", chi, "4m", "ron", false));
+        assert!(evaluation.complete);
+        assert!(evaluation.best.is_none());
+        assert!(evaluation.interpretations.iter().all(|reading| reading.han == 0 && reading.value.is_none()));
+    }
+
+    #[test]
+    fn a_bad_winning_tile_is_reported_instead_of_panicking() {
+        let codes = |winning_tile| {
+            let errors = request("234m567m345p678s88p", json!([]), winning_tile, "ron", false).parse().unwrap_err();
+            errors.violations.into_iter().map(|v| (v.field, v.code)).collect::<Vec<_>>()
+        };
+        assert_eq!(codes("45m"), [("winning_tile".to_string(), "invalid_tiles".to_string())]);
+        assert_eq!(codes("4x"), [("winning_tile".to_string(), "invalid_tiles".to_string())]);
+        assert_eq!(codes("1z"), [("winning_tile".to_string(), "not_in_hand".to_string())]);
+    }
+
+    fn violation_codes(request: EvaluateRequest) -> Vec<String> {
+        request.parse().map_or_else(|errors| errors.violations.into_iter().map(|v| v.code).collect(), |_| Vec::new())
+    }
+
+    #[test]
+    fn red_fives_are_counted_per_suit() {
+        assert_eq!(parse_tiles("0m0p0s").unwrap().1, [1, 1, 1]);
+        assert_eq!(parse_tiles("340m55p").unwrap().1, [1, 0, 0]);
+        assert!(parse_tiles("00m").is_err());
+        assert!(parse_tiles("0m123p0m").is_err());
+    }
+
+    #[test]
+    fn only_one_red_five_per_suit_across_hand_melds_and_indicators() {
+        let pon = json!([{ "kind": "pon", "tiles": "055m" }]);
+        let red_pon = request("406m567p345s88s", pon, "4m", "ron", false);
+        assert_eq!(violation_codes(red_pon), ["too_many_red_fives"]);
+
+        let mut red_indicator = request("406m567p345s88s123s", json!([]), "4m", "ron", false);
+        red_indicator.dora_indicators = "0m".to_string();
+        assert_eq!(violation_codes(red_indicator), ["too_many_red_fives"]);
+
+        // A red five in another suit is fine, and an indicator is not aka dora
+        let mut other_suit = request("406m567p345s88s123s", json!([]), "4m", "ron", false);
+        other_suit.dora_indicators = "0p".to_string();
+        assert_eq!(other_suit.parse().unwrap().red_fives, 1);
+    }
+
+    #[test]
+    fn dora_indicators_count_towards_the_copies_of_a_tile() {
+        let mut three_in_hand = request("222m444p666s345m88s", json!([]), "6s", "tsumo", false);
+        three_in_hand.dora_indicators = "2m".to_string();
+        assert!(three_in_hand.parse().is_ok());
+
+        three_in_hand.dora_indicators = "2m1z".to_string();
+        three_in_hand.situation.riichi = true;
+        three_in_hand.ura_dora_indicators = "2m".to_string();
+        assert_eq!(violation_codes(three_in_hand), ["too_many_copies"]);
+    }
+}
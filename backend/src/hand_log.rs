@@ -0,0 +1,725 @@
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgPool;
+use std::collections::{HashMap, HashSet};
+use utoipa::ToSchema;
+
+use crate::error::AppError;
+use crate::hand_score::{self, WinType};
+use crate::ruleset::Ruleset;
+use crate::validation::{ValidationErrors, PLAYERS_PER_GAME};
+use crate::{seat_wind_of, CreateGameRequest, SeatWind};
+
+/// Points a riichi declaration puts on the table.
+pub const RIICHI_DEPOSIT: i32 = 1000;
+/// Points the noten players pay the tenpai players at an exhaustive draw.
+const NOTEN_PAYMENT: i32 = 3000;
+/// Game points are recorded in hundreds of points.
+const POINTS_PER_GAME_POINT: i32 = 100;
+
+/// How a hand ended.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HandOutcome {
+    /// Win off a discard; more than one winner for a double or triple ron
+    Ron,
+    /// Self-drawn win
+    Tsumo,
+    /// Exhaustive draw, settled with tenpai payments
+    Draw,
+    /// Hand abandoned without payments, e.g. nine terminals or four riichi
+    AbortiveDraw,
+    /// Hand voided by a penalty; riichi deposits are returned
+    Chombo,
+}
+
+impl HandOutcome {
+    fn as_str(self) -> &'static str {
+        match self {
+            HandOutcome::Ron => "ron",
+            HandOutcome::Tsumo => "tsumo",
+            HandOutcome::Draw => "draw",
+            HandOutcome::AbortiveDraw => "abortive_draw",
+            HandOutcome::Chombo => "chombo",
+        }
+    }
+
+    fn from_db(value: &str) -> Option<HandOutcome> {
+        match value {
+            "ron" => Some(HandOutcome::Ron),
+            "tsumo" => Some(HandOutcome::Tsumo),
+            "draw" => Some(HandOutcome::Draw),
+            "abortive_draw" => Some(HandOutcome::AbortiveDraw),
+            "chombo" => Some(HandOutcome::Chombo),
+            _ => None,
+        }
+    }
+
+    fn is_win(self) -> bool {
+        matches!(self, HandOutcome::Ron | HandOutcome::Tsumo)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct HandWin {
+    seat_wind: SeatWind,
+    han: i32,
+    /// May be left out from 5 han, where fu does not matter
+    fu: Option<i32>,
+    /// Number of yakuman for a yakuman hand; overrides han
+    yakuman: Option<i32>,
+}
+
+/// One hand (kyoku) of a game. Seats are the players' starting winds; the
+/// dealer of hand N is the player who started in seat N, so East deals
+/// hand 1 of every round.
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct Hand {
+    round_wind: SeatWind,
+    /// 1 to 4 within the round
+    hand_number: i32,
+    #[serde(default)]
+    honba: i32,
+    /// Riichi deposits on the table when the hand starts
+    #[serde(default)]
+    riichi_sticks: i32,
+    outcome: HandOutcome,
+    /// Winners with their han and fu; only for ron and tsumo
+    #[serde(default)]
+    wins: Vec<HandWin>,
+    /// The player who dealt into a ron
+    discarder: Option<SeatWind>,
+    /// The player penalised for a chombo
+    chombo: Option<SeatWind>,
+    /// Players who declared riichi during the hand
+    #[serde(default)]
+    riichi: Vec<SeatWind>,
+    /// Players who called a tile (chi, pon or open kan) during the hand
+    #[serde(default)]
+    called: Vec<SeatWind>,
+    /// Players tenpai at an exhaustive draw
+    #[serde(default)]
+    tenpai: Vec<SeatWind>,
+    /// Net change of each seat's score in points, East first. Includes
+    /// riichi deposits paid and collected.
+    point_changes: Vec<i32>,
+}
+
+impl Hand {
+    fn dealer(&self) -> Option<SeatWind> {
+        usize::try_from(self.hand_number - 1)
+            .ok()
+            .and_then(|seat| SeatWind::ALL.get(seat).copied())
+    }
+
+    fn change(&self, seat: SeatWind) -> i32 {
+        self.point_changes[seat as usize]
+    }
+}
+
+/// Checks the game's hand log, if it has one: every hand on its own, with
+/// win payments worked out under `ruleset`, then that the log adds up to the
+/// recorded game points and leftover deposits.
+pub fn validate_hands(request: &CreateGameRequest, ruleset: &Ruleset, errors: &mut ValidationErrors) {
+    let Some(hands) = &request.hands else {
+        return;
+    };
+    let violations_before = errors.violations.len();
+
+    let mut totals = [0; PLAYERS_PER_GAME];
+    let mut deposits = 0;
+    for (index, hand) in hands.iter().enumerate() {
+        let field = format!("hands[{}]", index);
+        if hand.riichi_sticks != deposits {
+            errors.push(
+                format!("{}.riichi_sticks", field),
+                "riichi_sticks_mismatch",
+                format!("{} riichi deposits are left on the table by the earlier hands, got {}", deposits, hand.riichi_sticks),
+            );
+        }
+        deposits = validate_hand(hand, &field, ruleset, errors);
+        if hand.point_changes.len() == PLAYERS_PER_GAME {
+            for (total, change) in totals.iter_mut().zip(&hand.point_changes) {
+                *total += change;
+            }
+        }
+    }
+
+    // Comparing totals of a broken log would only repeat its errors
+    if errors.violations.len() > violations_before {
+        return;
+    }
+    for (index, result) in request.player_results.iter().enumerate() {
+        let Some(seat) = seat_wind_of(&request.player_results, index) else {
+            continue;
+        };
+        let total = totals[seat as usize];
+        if total != result.game_point * POINTS_PER_GAME_POINT {
+            errors.push(
+                format!("player_results[{}].game_point", index),
+                "hand_log_mismatch",
+                format!("the hand log adds up to {} points for the {} seat, not {}", total, seat.as_str(), result.game_point * POINTS_PER_GAME_POINT),
+            );
+        }
+    }
+    let leftover = deposits * RIICHI_DEPOSIT / POINTS_PER_GAME_POINT;
+    if leftover != request.forfeit_game_point.unwrap_or(0) {
+        errors.push(
+            "forfeit_game_point",
+            "hand_log_mismatch",
+            format!("the hand log leaves {} riichi deposits ({}) on the table", deposits, leftover),
+        );
+    }
+}
+
+/// Checks one hand, returning the riichi deposits left on the table after it.
+fn validate_hand(hand: &Hand, field: &str, ruleset: &Ruleset, errors: &mut ValidationErrors) -> i32 {
+    let violations_before = errors.violations.len();
+    if !(1..=4).contains(&hand.hand_number) {
+        errors.push(format!("{}.hand_number", field), "hand_number_range", "hand_number must be between 1 and 4");
+    }
+    if hand.honba < 0 {
+        errors.push(format!("{}.honba", field), "negative", "honba must not be negative");
+    }
+    if hand.riichi_sticks < 0 {
+        errors.push(format!("{}.riichi_sticks", field), "negative", "riichi_sticks must not be negative");
+    }
+    check_distinct(&hand.riichi, &format!("{}.riichi", field), errors);
+    check_distinct(&hand.called, &format!("{}.called", field), errors);
+    check_distinct(&hand.tenpai, &format!("{}.tenpai", field), errors);
+    let winners: Vec<SeatWind> = hand.wins.iter().map(|win| win.seat_wind).collect();
+    check_distinct(&winners, &format!("{}.wins", field), errors);
+
+    for (index, win) in hand.wins.iter().enumerate() {
+        hand_score::validate_value(win.han, win.fu, win.yakuman, errors, &format!("{}.wins[{}].", field, index));
+    }
+
+    match hand.outcome {
+        HandOutcome::Ron => {
+            if hand.wins.is_empty() || hand.wins.len() >= PLAYERS_PER_GAME {
+                errors.push(format!("{}.wins", field), "winner_count", "a ron needs one to three winners");
+            }
+            match hand.discarder {
+                None => errors.push(format!("{}.discarder", field), "missing_discarder", "a ron needs the discarder"),
+                Some(discarder) if winners.contains(&discarder) => {
+                    errors.push(format!("{}.discarder", field), "discarder_is_winner", "the discarder cannot also win")
+                }
+                Some(_) => {}
+            }
+        }
+        HandOutcome::Tsumo => {
+            if hand.wins.len() != 1 {
+                errors.push(format!("{}.wins", field), "winner_count", "a tsumo has exactly one winner");
+            }
+        }
+        HandOutcome::Draw | HandOutcome::AbortiveDraw | HandOutcome::Chombo => {
+            if !hand.wins.is_empty() {
+                errors.push(format!("{}.wins", field), "unexpected_winner", "only a ron or tsumo has winners");
+            }
+        }
+    }
+    if hand.outcome != HandOutcome::Ron && hand.discarder.is_some() {
+        errors.push(format!("{}.discarder", field), "unexpected_discarder", "only a ron has a discarder");
+    }
+    if hand.outcome != HandOutcome::Draw && !hand.tenpai.is_empty() {
+        errors.push(format!("{}.tenpai", field), "unexpected_tenpai", "tenpai is only recorded at an exhaustive draw");
+    }
+    match (hand.outcome, hand.chombo) {
+        (HandOutcome::Chombo, None) => {
+            errors.push(format!("{}.chombo", field), "missing_chombo", "a chombo needs the penalised player")
+        }
+        (HandOutcome::Chombo, Some(_)) => {}
+        (_, Some(_)) => errors.push(format!("{}.chombo", field), "unexpected_chombo", "only a chombo has a penalised player"),
+        (_, None) => {}
+    }
+
+    // Deposits of a chombo hand are handed back, so they never reach the table
+    let declared = match hand.outcome {
+        HandOutcome::Chombo => 0,
+        _ => hand.riichi.len() as i32,
+    };
+    let deposits_after = if hand.outcome.is_win() { 0 } else { hand.riichi_sticks + declared };
+
+    if hand.point_changes.len() != PLAYERS_PER_GAME {
+        errors.push(
+            format!("{}.point_changes", field),
+            "point_changes_length",
+            format!("point_changes needs one value per seat ({}), got {}", PLAYERS_PER_GAME, hand.point_changes.len()),
+        );
+        return deposits_after;
+    }
+    for (seat, change) in hand.point_changes.iter().enumerate() {
+        if change % POINTS_PER_GAME_POINT != 0 {
+            errors.push(
+                format!("{}.point_changes[{}]", field, seat),
+                "not_whole_hundreds",
+                "point changes must be whole hundreds",
+            );
+        }
+    }
+
+    match hand.outcome {
+        HandOutcome::Draw => check_draw_payments(hand, field, errors),
+        HandOutcome::AbortiveDraw => {
+            for seat in SeatWind::ALL {
+                let expected = if hand.riichi.contains(&seat) { -RIICHI_DEPOSIT } else { 0 };
+                if hand.change(seat) != expected {
+                    errors.push(
+                        format!("{}.point_changes[{}]", field, seat as usize),
+                        "abortive_draw_payment",
+                        format!("an abortive draw only takes riichi deposits; expected {}", expected),
+                    );
+                }
+            }
+        }
+        // Payments of a malformed win would only repeat its errors
+        HandOutcome::Ron | HandOutcome::Tsumo => {
+            if errors.violations.len() == violations_before {
+                check_win_payments(hand, field, ruleset, errors);
+            }
+        }
+        HandOutcome::Chombo => {
+            let sum: i32 = hand.point_changes.iter().sum();
+            if sum != 0 {
+                errors.push(
+                    format!("{}.point_changes", field),
+                    "point_change_sum",
+                    format!("point changes of a chombo sum to {}, expected 0", sum),
+                );
+            }
+        }
+    }
+    if let Some(penalised) = hand.chombo {
+        if hand.outcome == HandOutcome::Chombo && hand.change(penalised) >= 0 {
+            errors.push(
+                format!("{}.point_changes[{}]", field, penalised as usize),
+                "chombo_not_paid",
+                "the penalised player must lose points",
+            );
+        }
+    }
+
+    deposits_after
+}
+
+/// Checks the noten payments of an exhaustive draw: the noten players pay
+/// 3000 points between them to the tenpai players, nothing moves when all or
+/// nobody is tenpai, and riichi declarers also pay their deposit.
+fn check_draw_payments(hand: &Hand, field: &str, errors: &mut ValidationErrors) {
+    let tenpai_count = SeatWind::ALL.iter().filter(|seat| hand.tenpai.contains(seat)).count() as i32;
+    let noten_count = PLAYERS_PER_GAME as i32 - tenpai_count;
+    for seat in SeatWind::ALL {
+        let payment = match (tenpai_count, hand.tenpai.contains(&seat)) {
+            (0, _) | (4, _) => 0,
+            (_, true) => NOTEN_PAYMENT / tenpai_count,
+            (_, false) => -NOTEN_PAYMENT / noten_count,
+        };
+        let deposit = if hand.riichi.contains(&seat) { RIICHI_DEPOSIT } else { 0 };
+        let expected = payment - deposit;
+        if hand.change(seat) != expected {
+            errors.push(
+                format!("{}.point_changes[{}]", field, seat as usize),
+                "tenpai_payment",
+                format!("the {} seat should change by {} at this draw, got {}", seat.as_str(), expected, hand.change(seat)),
+            );
+        }
+    }
+}
+
+/// Checks a win's point changes against the payments its han and fu call
+/// for. On a multiple ron the honba and the deposits on the table go to the
+/// winner first in turn after the discarder.
+fn check_win_payments(hand: &Hand, field: &str, ruleset: &Ruleset, errors: &mut ValidationErrors) {
+    let Some(dealer) = hand.dealer() else {
+        return;
+    };
+    let win_type = match hand.outcome {
+        HandOutcome::Tsumo => WinType::Tsumo,
+        _ => WinType::Ron,
+    };
+
+    let mut expected = [0; PLAYERS_PER_GAME];
+    for seat in &hand.riichi {
+        expected[*seat as usize] -= RIICHI_DEPOSIT;
+    }
+    let mut wins: Vec<&HandWin> = hand.wins.iter().collect();
+    if let Some(discarder) = hand.discarder {
+        wins.sort_by_key(|win| (win.seat_wind as usize + PLAYERS_PER_GAME - discarder as usize) % PLAYERS_PER_GAME);
+    }
+    for (index, win) in wins.into_iter().enumerate() {
+        let Some(value) = hand_score::hand_value(win.han, win.fu, win.yakuman, ruleset.kiriage_mangan) else {
+            return;
+        };
+        let (honba, riichi_sticks) = match index {
+            0 => (hand.honba, hand.riichi_sticks + hand.riichi.len() as i32),
+            _ => (0, 0),
+        };
+        let payments = hand_score::payments(&value, win.seat_wind == dealer, win_type, honba, riichi_sticks);
+        expected[win.seat_wind as usize] += payments.total;
+        match hand.discarder {
+            Some(discarder) => expected[discarder as usize] -= payments.ron.unwrap_or(0),
+            None => {
+                for seat in SeatWind::ALL.into_iter().filter(|&seat| seat != win.seat_wind) {
+                    let payment = if seat == dealer { payments.from_dealer } else { payments.from_each_non_dealer };
+                    expected[seat as usize] -= payment.unwrap_or(0);
+                }
+            }
+        }
+    }
+
+    for seat in SeatWind::ALL {
+        if hand.change(seat) != expected[seat as usize] {
+            errors.push(
+                format!("{}.point_changes[{}]", field, seat as usize),
+                "payment_mismatch",
+                format!(
+                    "the {} seat should change by {} for this win, got {}",
+                    seat.as_str(),
+                    expected[seat as usize],
+                    hand.change(seat)
+                ),
+            );
+        }
+    }
+}
+
+fn check_distinct(seats: &[SeatWind], field: &str, errors: &mut ValidationErrors) {
+    let mut seen = HashSet::new();
+    for (index, seat) in seats.iter().enumerate() {
+        if !seen.insert(seat) {
+            errors.push(
+                format!("{}[{}]", field, index),
+                "duplicate_seat",
+                format!("the {} seat is listed more than once", seat.as_str()),
+            );
+        }
+    }
+}
+
+/// Stores a validated hand log for a game that has none.
+pub async fn insert_hands(conn: &mut sqlx::PgConnection, game_id: i32, hands: &[Hand]) -> Result<(), AppError> {
+    for (index, hand) in hands.iter().enumerate() {
+        let hand_id = sqlx::query_scalar::<_, i32>(
+            r#"
+            INSERT INTO game_hands (game_id, hand_index, round_wind, hand_number, honba, riichi_sticks, outcome, discarder_seat, chombo_seat)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING id
+            "#
+        )
+        .bind(game_id)
+        .bind(index as i32 + 1)
+        .bind(hand.round_wind.as_str())
+        .bind(hand.hand_number)
+        .bind(hand.honba)
+        .bind(hand.riichi_sticks)
+        .bind(hand.outcome.as_str())
+        .bind(hand.discarder.map(SeatWind::as_str))
+        .bind(hand.chombo.map(SeatWind::as_str))
+        .fetch_one(&mut *conn)
+        .await?;
+
+        let win = |seat: SeatWind| hand.wins.iter().find(|win| win.seat_wind == seat);
+        let seats: Vec<&str> = SeatWind::ALL.iter().map(|seat| seat.as_str()).collect();
+        let riichi: Vec<bool> = SeatWind::ALL.iter().map(|seat| hand.riichi.contains(seat)).collect();
+        let called: Vec<bool> = SeatWind::ALL.iter().map(|seat| hand.called.contains(seat)).collect();
+        let tenpai: Vec<bool> = SeatWind::ALL.iter().map(|seat| hand.tenpai.contains(seat)).collect();
+        let han: Vec<Option<i32>> = SeatWind::ALL.iter().map(|&seat| win(seat).map(|win| win.han)).collect();
+        let fu: Vec<Option<i32>> = SeatWind::ALL.iter().map(|&seat| win(seat).and_then(|win| win.fu)).collect();
+        let yakuman: Vec<Option<i32>> = SeatWind::ALL.iter().map(|&seat| win(seat).and_then(|win| win.yakuman)).collect();
+        sqlx::query(
+            r#"
+            INSERT INTO game_hand_seats (hand_id, seat_wind, point_change, riichi, called, tenpai, han, fu, yakuman)
+            SELECT $1, * FROM UNNEST($2::TEXT[], $3::INTEGER[], $4::BOOLEAN[], $5::BOOLEAN[], $6::BOOLEAN[], $7::INTEGER[], $8::INTEGER[], $9::INTEGER[])
+            "#
+        )
+        .bind(hand_id)
+        .bind(&seats)
+        .bind(&hand.point_changes)
+        .bind(&riichi)
+        .bind(&called)
+        .bind(&tenpai)
+        .bind(&han)
+        .bind(&fu)
+        .bind(&yakuman)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Loads a game's hand log in playing order; empty when none was recorded.
+pub async fn load_hands(pool: &PgPool, game_id: i32) -> Result<Vec<Hand>, AppError> {
+    #[derive(sqlx::FromRow)]
+    struct HandRow {
+        id: i32,
+        round_wind: String,
+        hand_number: i32,
+        honba: i32,
+        riichi_sticks: i32,
+        outcome: String,
+        discarder_seat: Option<String>,
+        chombo_seat: Option<String>,
+    }
+
+    #[derive(sqlx::FromRow)]
+    struct SeatRow {
+        hand_id: i32,
+        seat_wind: String,
+        point_change: i32,
+        riichi: bool,
+        called: bool,
+        tenpai: bool,
+        han: Option<i32>,
+        fu: Option<i32>,
+        yakuman: Option<i32>,
+    }
+
+    let hand_rows = sqlx::query_as::<_, HandRow>(
+        r#"
+        SELECT id, round_wind, hand_number, honba, riichi_sticks, outcome, discarder_seat, chombo_seat
+        FROM game_hands
+        WHERE game_id = $1
+        ORDER BY hand_index
+        "#
+    )
+    .bind(game_id)
+    .fetch_all(pool)
+    .await?;
+
+    let seat_rows = sqlx::query_as::<_, SeatRow>(
+        r#"
+        SELECT hs.hand_id, hs.seat_wind, hs.point_change, hs.riichi, hs.called, hs.tenpai, hs.han, hs.fu, hs.yakuman
+        FROM game_hand_seats hs
+        JOIN game_hands h ON h.id = hs.hand_id
+        WHERE h.game_id = $1
+        "#
+    )
+    .bind(game_id)
+    .fetch_all(pool)
+    .await?;
+
+    let wind = |value: &str| {
+        SeatWind::from_db(value).ok_or_else(|| AppError::internal(format!("hand log of game {} has an unknown wind", game_id)))
+    };
+    let mut seats_by_hand: HashMap<i32, Vec<(SeatWind, SeatRow)>> = HashMap::new();
+    for row in seat_rows {
+        let seat = wind(&row.seat_wind)?;
+        seats_by_hand.entry(row.hand_id).or_default().push((seat, row));
+    }
+
+    hand_rows
+        .into_iter()
+        .map(|row| {
+            let mut seats = seats_by_hand.remove(&row.id).unwrap_or_default();
+            seats.sort_by_key(|&(seat, _)| seat);
+            Ok(Hand {
+                round_wind: wind(&row.round_wind)?,
+                hand_number: row.hand_number,
+                honba: row.honba,
+                riichi_sticks: row.riichi_sticks,
+                outcome: HandOutcome::from_db(&row.outcome)
+                    .ok_or_else(|| AppError::internal(format!("hand log of game {} has an unknown outcome", game_id)))?,
+                wins: seats
+                    .iter()
+                    .filter_map(|(seat, s)| s.han.map(|han| HandWin { seat_wind: *seat, han, fu: s.fu, yakuman: s.yakuman }))
+                    .collect(),
+                discarder: row.discarder_seat.as_deref().map(wind).transpose()?,
+                chombo: row.chombo_seat.as_deref().map(wind).transpose()?,
+                riichi: seats.iter().filter(|(_, s)| s.riichi).map(|(seat, _)| *seat).collect(),
+                called: seats.iter().filter(|(_, s)| s.called).map(|(seat, _)| *seat).collect(),
+                tenpai: seats.iter().filter(|(_, s)| s.tenpai).map(|(seat, _)| *seat).collect(),
+                point_changes: seats.iter().map(|(_, s)| s.point_change).collect(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validation::tests::{game, request};
+    use serde_json::{json, Value};
+
+    /// Codes reported for a game with these game points (East first), forfeit
+    /// and hand log.
+    fn codes(game_points: [i32; 4], forfeit_game_point: i32, hands: Value) -> Vec<String> {
+        let mut value = game(game_points);
+        value["forfeit_game_point"] = json!(forfeit_game_point);
+        value["hands"] = hands;
+        let mut errors = ValidationErrors::default();
+        validate_hands(&request(value), &Ruleset::standard(), &mut errors);
+        errors.violations.into_iter().map(|v| v.code).collect()
+    }
+
+    /// Codes reported for one hand on its own.
+    fn hand_codes(hand: Value) -> Vec<String> {
+        let hand: Hand = serde_json::from_value(hand).unwrap();
+        let mut errors = ValidationErrors::default();
+        validate_hand(&hand, "hands[0]", &Ruleset::standard(), &mut errors);
+        errors.violations.into_iter().map(|v| v.code).collect()
+    }
+
+    /// A hand in the East round with the given outcome fields.
+    fn east(hand_number: i32, honba: i32, riichi_sticks: i32, outcome: Value) -> Value {
+        let mut hand = json!({
+            "round_wind": "east",
+            "hand_number": hand_number,
+            "honba": honba,
+            "riichi_sticks": riichi_sticks,
+        });
+        hand.as_object_mut().unwrap().extend(outcome.as_object().unwrap().clone());
+        hand
+    }
+
+    /// East 1: the dealer rons West for 2 han 30 fu and takes South's riichi
+    /// deposit. East 1-1: East and North tenpai. East 1-2: South tsumo for
+    /// 1 han 30 fu.
+    fn three_hands() -> Value {
+        json!([
+            east(1, 0, 0, json!({
+                "outcome": "ron",
+                "wins": [{ "seat_wind": "east", "han": 2, "fu": 30 }],
+                "discarder": "west",
+                "riichi": ["south"],
+                "point_changes": [3900, -1000, -2900, 0],
+            })),
+            east(1, 1, 0, json!({
+                "outcome": "draw",
+                "tenpai": ["east", "north"],
+                "point_changes": [1500, -1500, -1500, 1500],
+            })),
+            east(1, 2, 0, json!({
+                "outcome": "tsumo",
+                "wins": [{ "seat_wind": "south", "han": 1, "fu": 30 }],
+                "point_changes": [-700, 1700, -500, -500],
+            })),
+        ])
+    }
+
+    #[test]
+    fn accepts_a_log_that_adds_up_to_the_game_points() {
+        assert!(codes([47, -8, -49, 10], 0, three_hands()).is_empty());
+    }
+
+    #[test]
+    fn log_totals_must_match_the_game_points() {
+        assert_eq!(codes([-8, 47, -49, 10], 0, three_hands()), ["hand_log_mismatch", "hand_log_mismatch"]);
+    }
+
+    #[test]
+    fn deposits_carry_over_to_the_next_hand_and_the_forfeit() {
+        let draw = east(1, 0, 0, json!({
+            "outcome": "draw",
+            "riichi": ["east"],
+            "tenpai": ["east"],
+            "point_changes": [2000, -1000, -1000, -1000],
+        }));
+        assert!(codes([20, -10, -10, -10], 10, json!([draw])).is_empty());
+        assert_eq!(codes([20, -10, -10, -10], 0, json!([draw])), ["hand_log_mismatch"]);
+
+        let ron = |riichi_sticks| {
+            east(1, 1, riichi_sticks, json!({
+                "outcome": "ron",
+                "wins": [{ "seat_wind": "south", "han": 1, "fu": 30 }],
+                "discarder": "north",
+                "point_changes": [0, 2300, 0, -1300],
+            }))
+        };
+        assert!(codes([20, 13, -10, -23], 0, json!([draw, ron(1)])).is_empty());
+        assert_eq!(
+            codes([20, 13, -10, -23], 0, json!([draw, ron(0)])),
+            ["riichi_sticks_mismatch", "payment_mismatch"]
+        );
+    }
+
+    #[test]
+    fn noten_players_pay_3000_between_them() {
+        let cases: [(&[&str], [i32; 4]); 5] = [
+            (&[], [0, 0, 0, 0]),
+            (&["south"], [-1000, 3000, -1000, -1000]),
+            (&["east", "west"], [1500, -1500, 1500, -1500]),
+            (&["east", "south", "north"], [1000, 1000, -3000, 1000]),
+            (&["east", "south", "west", "north"], [0, 0, 0, 0]),
+        ];
+        for (tenpai, point_changes) in cases {
+            let hand = |point_changes: [i32; 4]| {
+                east(1, 0, 0, json!({ "outcome": "draw", "tenpai": tenpai, "point_changes": point_changes }))
+            };
+            assert!(hand_codes(hand(point_changes)).is_empty(), "tenpai {:?}", tenpai);
+            let mut wrong = point_changes;
+            wrong[0] += 100;
+            wrong[1] -= 100;
+            assert_eq!(hand_codes(hand(wrong)), ["tenpai_payment", "tenpai_payment"], "tenpai {:?}", tenpai);
+        }
+    }
+
+    #[test]
+    fn dealer_tsumo_splits_honba_between_the_payers() {
+        let hand = east(1, 2, 0, json!({
+            "outcome": "tsumo",
+            "wins": [{ "seat_wind": "east", "han": 1, "fu": 30 }],
+            "point_changes": [2100, -700, -700, -700],
+        }));
+        assert!(hand_codes(hand).is_empty());
+    }
+
+    #[test]
+    fn double_ron_gives_honba_and_deposits_to_the_first_winner_after_the_discarder() {
+        // South deals; North deals in to East (next in turn) and West
+        let hand = |point_changes: [i32; 4]| {
+            east(2, 1, 1, json!({
+                "outcome": "ron",
+                "wins": [
+                    { "seat_wind": "west", "han": 1, "fu": 30 },
+                    { "seat_wind": "east", "han": 2, "fu": 30 },
+                ],
+                "discarder": "north",
+                "point_changes": point_changes,
+            }))
+        };
+        assert!(hand_codes(hand([3300, 0, 1000, -3300])).is_empty());
+        assert_eq!(hand_codes(hand([2000, 0, 2300, -3300])), ["payment_mismatch", "payment_mismatch"]);
+    }
+
+    #[test]
+    fn malformed_wins_are_reported_without_checking_payments() {
+        let ron = |discarder: Option<&str>| {
+            east(1, 0, 0, json!({
+                "outcome": "ron",
+                "wins": [{ "seat_wind": "east", "han": 1, "fu": 30 }],
+                "discarder": discarder,
+                "point_changes": [0, 0, 0, 0],
+            }))
+        };
+        assert_eq!(hand_codes(ron(None)), ["missing_discarder"]);
+        assert_eq!(hand_codes(ron(Some("east"))), ["discarder_is_winner"]);
+
+        let tsumo = east(1, 0, 0, json!({
+            "outcome": "tsumo",
+            "wins": [{ "seat_wind": "east", "han": 1, "fu": 30 }, { "seat_wind": "south", "han": 1, "fu": 30 }],
+            "point_changes": [0, 0, 0, 0],
+        }));
+        assert_eq!(hand_codes(tsumo), ["winner_count"]);
+    }
+
+    #[test]
+    fn abortive_draws_and_chombo_keep_riichi_deposits_on_the_table() {
+        let abortive = east(3, 0, 0, json!({
+            "outcome": "abortive_draw",
+            "riichi": ["west"],
+            "point_changes": [0, 0, -1000, 0],
+        }));
+        assert!(hand_codes(abortive).is_empty());
+
+        let chombo = |point_changes: [i32; 4]| {
+            east(1, 0, 0, json!({
+                "outcome": "chombo",
+                "chombo": "south",
+                "point_changes": point_changes,
+            }))
+        };
+        assert!(hand_codes(chombo([4000, -8000, 2000, 2000])).is_empty());
+        assert_eq!(hand_codes(chombo([-4000, 8000, -2000, -2000])), ["chombo_not_paid"]);
+        assert_eq!(hand_codes(chombo([4000, -8000, 2000, 1000])), ["point_change_sum"]);
+    }
+}
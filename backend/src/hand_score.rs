@@ -0,0 +1,333 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::error::{AppError, Problem};
+use crate::extract::Json;
+use crate::hand_log::RIICHI_DEPOSIT;
+use crate::validation::ValidationErrors;
+
+/// Base points of a mangan; every limit hand is a multiple of it.
+const MANGAN_BASE_POINTS: i32 = 2000;
+/// Base points of 4 han 30 fu and 3 han 60 fu, which kiriage mangan rounds up.
+const KIRIAGE_BASE_POINTS: i32 = 1920;
+/// Points each honba adds to a win, split between the payers on a tsumo.
+const HONBA_POINTS: i32 = 300;
+/// Highest fu a hand can score.
+const MAX_FU: i32 = 110;
+/// Most yakuman a single hand can combine.
+const MAX_YAKUMAN: i32 = 6;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WinType {
+    Ron,
+    Tsumo,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Limit {
+    Mangan,
+    Haneman,
+    Baiman,
+    Sanbaiman,
+    Yakuman,
+}
+
+/// What a hand is worth before anyone pays: the base points every payment
+/// is a multiple of.
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct HandValue {
+    pub han: i32,
+    /// Rounded up to the next 10, except 25 for seven pairs; absent when a
+    /// limit makes fu irrelevant and none was given
+    pub fu: Option<i32>,
+    pub yakuman: Option<i32>,
+    pub limit: Option<Limit>,
+    pub base_points: i32,
+}
+
+/// Who pays the winner what. Payments include honba; riichi deposits come
+/// from the table.
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct Payments {
+    /// Ron: paid by the discarder
+    pub ron: Option<i32>,
+    /// Non-dealer tsumo: paid by the dealer
+    pub from_dealer: Option<i32>,
+    /// Tsumo: paid by each non-dealer
+    pub from_each_non_dealer: Option<i32>,
+    /// The hand's value, without honba or deposits
+    pub hand_points: i32,
+    pub honba_points: i32,
+    pub riichi_deposits: i32,
+    /// Everything the winner receives
+    pub total: i32,
+}
+
+/// Fu as they are scored: rounded up to the next 10, except seven pairs'
+/// fixed 25.
+pub fn round_fu(fu: i32) -> i32 {
+    if fu == 25 {
+        fu
+    } else {
+        (fu + 9) / 10 * 10
+    }
+}
+
+/// Checks han, fu and yakuman count, reporting violations under `prefix`.
+pub fn validate_value(han: i32, fu: Option<i32>, yakuman: Option<i32>, errors: &mut ValidationErrors, prefix: &str) {
+    if han <= 0 {
+        errors.push(format!("{}han", prefix), "not_positive", "han must be greater than 0");
+    }
+    if yakuman.is_some_and(|count| !(1..=MAX_YAKUMAN).contains(&count)) {
+        errors.push(
+            format!("{}yakuman", prefix),
+            "yakuman_range",
+            format!("yakuman must be between 1 and {}", MAX_YAKUMAN),
+        );
+    }
+    match fu {
+        Some(fu) if !(20..=MAX_FU).contains(&fu) => {
+            errors.push(format!("{}fu", prefix), "fu_range", format!("fu must be between 20 and {}", MAX_FU))
+        }
+        None if yakuman.is_none() && (1..5).contains(&han) => {
+            errors.push(format!("{}fu", prefix), "missing_fu", "fu is needed below 5 han")
+        }
+        _ => {}
+    }
+}
+
+/// Values a hand of `han` and `fu`, or `yakuman` yakuman when given.
+/// Returns `None` when fu is needed and missing; see `validate_value`.
+pub fn hand_value(han: i32, fu: Option<i32>, yakuman: Option<i32>, kiriage_mangan: bool) -> Option<HandValue> {
+    let fu = fu.map(round_fu);
+    let (limit, base_points) = match (yakuman, han) {
+        (Some(count), _) => (Some(Limit::Yakuman), MANGAN_BASE_POINTS * 4 * count),
+        // Counted yakuman
+        (None, 13..) => (Some(Limit::Yakuman), MANGAN_BASE_POINTS * 4),
+        (None, 11..=12) => (Some(Limit::Sanbaiman), MANGAN_BASE_POINTS * 3),
+        (None, 8..=10) => (Some(Limit::Baiman), MANGAN_BASE_POINTS * 2),
+        (None, 6..=7) => (Some(Limit::Haneman), MANGAN_BASE_POINTS * 3 / 2),
+        (None, 5) => (Some(Limit::Mangan), MANGAN_BASE_POINTS),
+        (None, _) => {
+            let base_points = fu? << (han.max(0) + 2);
+            if base_points >= MANGAN_BASE_POINTS || (kiriage_mangan && base_points == KIRIAGE_BASE_POINTS) {
+                (Some(Limit::Mangan), MANGAN_BASE_POINTS)
+            } else {
+                (None, base_points)
+            }
+        }
+    };
+
+    Some(HandValue {
+        han,
+        fu,
+        yakuman,
+        limit,
+        base_points,
+    })
+}
+
+fn round_up_to_hundred(points: i32) -> i32 {
+    (points + 99) / 100 * 100
+}
+
+/// Splits a hand's value into payments. The winner also collects
+/// `riichi_sticks` deposits and `honba` counters from the table.
+pub fn payments(value: &HandValue, dealer: bool, win: WinType, honba: i32, riichi_sticks: i32) -> Payments {
+    let base = value.base_points;
+    let honba_points = HONBA_POINTS * honba;
+    let (ron, from_dealer, from_each_non_dealer, hand_points) = match (win, dealer) {
+        (WinType::Ron, true) => {
+            let points = round_up_to_hundred(base * 6);
+            (Some(points + honba_points), None, None, points)
+        }
+        (WinType::Ron, false) => {
+            let points = round_up_to_hundred(base * 4);
+            (Some(points + honba_points), None, None, points)
+        }
+        (WinType::Tsumo, true) => {
+            let each = round_up_to_hundred(base * 2);
+            (None, None, Some(each + honba_points / 3), each * 3)
+        }
+        (WinType::Tsumo, false) => {
+            let dealer_share = round_up_to_hundred(base * 2);
+            let each = round_up_to_hundred(base);
+            (None, Some(dealer_share + honba_points / 3), Some(each + honba_points / 3), dealer_share + each * 2)
+        }
+    };
+    let riichi_deposits = RIICHI_DEPOSIT * riichi_sticks;
+
+    Payments {
+        ron,
+        from_dealer,
+        from_each_non_dealer,
+        hand_points,
+        honba_points,
+        riichi_deposits,
+        total: hand_points + honba_points + riichi_deposits,
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct ScoreRequest {
+    han: i32,
+    /// Needed below 5 han; rounded up to the next 10 except for 25
+    fu: Option<i32>,
+    /// Number of yakuman, e.g. 2 for a double yakuman; overrides han
+    yakuman: Option<i32>,
+    /// Whether the winner is the dealer
+    #[serde(default)]
+    dealer: bool,
+    win: WinType,
+    #[serde(default)]
+    honba: i32,
+    /// Riichi deposits the winner collects from the table
+    #[serde(default)]
+    riichi_sticks: i32,
+    /// Round 4 han 30 fu and 3 han 60 fu up to a mangan
+    #[serde(default)]
+    kiriage_mangan: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct ScoreResult {
+    #[serde(flatten)]
+    #[schema(inline)]
+    value: HandValue,
+    #[serde(flatten)]
+    #[schema(inline)]
+    payments: Payments,
+}
+
+/// Works out the payments for a win, to settle disputes at the table.
+#[utoipa::path(
+    post,
+    path = "/v1/tools/score",
+    request_body = ScoreRequest,
+    responses(
+        (status = 200, description = "Hand value and payments", body = ScoreResult),
+        (status = 400, description = "Malformed request body", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid han, fu or counters", body = Problem, content_type = "application/problem+json")
+    )
+)]
+pub async fn calculate_score(Json(request): Json<ScoreRequest>) -> Result<Json<ScoreResult>, AppError> {
+    let mut errors = ValidationErrors::default();
+    validate_value(request.han, request.fu, request.yakuman, &mut errors, "");
+    if request.honba < 0 {
+        errors.push("honba", "negative", "honba must not be negative");
+    }
+    if request.riichi_sticks < 0 {
+        errors.push("riichi_sticks", "negative", "riichi_sticks must not be negative");
+    }
+    errors.into_result(())?;
+
+    let value = hand_value(request.han, request.fu, request.yakuman, request.kiriage_mangan)
+        .ok_or_else(|| AppError::internal("validated hand has no value"))?;
+    let payments = payments(&value, request.dealer, request.win, request.honba, request.riichi_sticks);
+    Ok(Json(ScoreResult { value, payments }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value(han: i32, fu: Option<i32>) -> HandValue {
+        hand_value(han, fu, None, false).unwrap()
+    }
+
+    #[test]
+    fn limits_start_at_their_han_or_base_point_boundaries() {
+        let cases = [
+            (4, Some(30), None, 1920),
+            (4, Some(40), Some(Limit::Mangan), 2000),
+            (3, Some(70), Some(Limit::Mangan), 2000),
+            (5, None, Some(Limit::Mangan), 2000),
+            (6, None, Some(Limit::Haneman), 3000),
+            (7, None, Some(Limit::Haneman), 3000),
+            (8, None, Some(Limit::Baiman), 4000),
+            (10, None, Some(Limit::Baiman), 4000),
+            (11, None, Some(Limit::Sanbaiman), 6000),
+            (12, None, Some(Limit::Sanbaiman), 6000),
+            (13, None, Some(Limit::Yakuman), 8000),
+            (20, None, Some(Limit::Yakuman), 8000),
+        ];
+        for (han, fu, limit, base_points) in cases {
+            let value = value(han, fu);
+            assert_eq!((value.limit, value.base_points), (limit, base_points), "{} han {:?} fu", han, fu);
+        }
+    }
+
+    #[test]
+    fn kiriage_rounds_1920_base_points_up_to_a_mangan() {
+        for (han, fu) in [(4, 30), (3, 60)] {
+            assert_eq!(hand_value(han, Some(fu), None, false).unwrap().base_points, 1920);
+            let value = hand_value(han, Some(fu), None, true).unwrap();
+            assert_eq!((value.limit, value.base_points), (Some(Limit::Mangan), 2000));
+        }
+        assert_eq!(hand_value(3, Some(50), None, true).unwrap().base_points, 1600);
+    }
+
+    #[test]
+    fn yakuman_count_multiplies_up_to_the_maximum() {
+        assert_eq!(hand_value(1, None, Some(2), false).unwrap().base_points, 16000);
+        assert_eq!(hand_value(1, None, Some(MAX_YAKUMAN), false).unwrap().base_points, 8000 * MAX_YAKUMAN);
+
+        for (count, valid) in [(0, false), (1, true), (MAX_YAKUMAN, true), (MAX_YAKUMAN + 1, false)] {
+            let mut errors = ValidationErrors::default();
+            validate_value(13, None, Some(count), &mut errors, "");
+            assert_eq!(errors.violations.is_empty(), valid, "{} yakuman", count);
+        }
+    }
+
+    #[test]
+    fn fu_rounds_up_to_ten_except_seven_pairs() {
+        assert_eq!([22, 25, 30, 32, 102].map(round_fu), [30, 25, 30, 40, 110]);
+        assert_eq!(value(2, Some(25)).base_points, 400);
+        assert!(hand_value(3, None, None, false).is_none());
+    }
+
+    #[test]
+    fn ron_payments_round_up_to_the_hundred() {
+        let value = value(4, Some(30));
+        assert_eq!(payments(&value, true, WinType::Ron, 0, 0).ron, Some(11600));
+        assert_eq!(payments(&value, false, WinType::Ron, 0, 0).ron, Some(7700));
+    }
+
+    #[test]
+    fn tsumo_payments_split_between_dealer_and_non_dealers() {
+        // 1 han 30 fu: 240 base points
+        let value = value(1, Some(30));
+
+        let dealer = payments(&value, true, WinType::Tsumo, 0, 0);
+        assert_eq!((dealer.from_dealer, dealer.from_each_non_dealer, dealer.total), (None, Some(500), 1500));
+
+        let non_dealer = payments(&value, false, WinType::Tsumo, 0, 0);
+        assert_eq!((non_dealer.from_dealer, non_dealer.from_each_non_dealer), (Some(500), Some(300)));
+        assert_eq!(non_dealer.total, 1100);
+    }
+
+    #[test]
+    fn honba_add_300_split_across_tsumo_payers() {
+        let value = value(1, Some(30));
+
+        let ron = payments(&value, false, WinType::Ron, 2, 0);
+        assert_eq!((ron.ron, ron.honba_points, ron.total), (Some(1600), 600, 1600));
+
+        let tsumo = payments(&value, false, WinType::Tsumo, 2, 0);
+        assert_eq!((tsumo.from_dealer, tsumo.from_each_non_dealer), (Some(700), Some(500)));
+        assert_eq!((tsumo.honba_points, tsumo.total), (600, 1700));
+    }
+
+    #[test]
+    fn riichi_deposits_go_to_the_winner_but_not_from_the_payers() {
+        let value = value(1, Some(30));
+
+        let ron = payments(&value, false, WinType::Ron, 0, 3);
+        assert_eq!((ron.ron, ron.riichi_deposits, ron.total), (Some(1000), 3000, 4000));
+
+        let tsumo = payments(&value, true, WinType::Tsumo, 0, 1);
+        assert_eq!((tsumo.from_each_non_dealer, tsumo.total), (Some(500), 2500));
+    }
+}
@@ -0,0 +1,295 @@
+use axum::extract::State;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgPool;
+use utoipa::ToSchema;
+
+use crate::error::{AppError, Problem};
+use crate::extract::{Json, Path, Query};
+use crate::{load_game_details, GameDetail, Player};
+
+/// How a player fared against one opponent in the games they shared.
+/// Placements come from table points, so a shared placement counts as the
+/// average of the places it spans (e.g. 2.5 for a tie for 2nd).
+#[derive(Serialize, Deserialize, sqlx::FromRow, Debug, Clone, ToSchema)]
+pub struct OpponentRecord {
+    opponent_id: i32,
+    opponent_name: String,
+    game_count: i64,
+    /// Games in which the player finished above the opponent
+    above_count: i64,
+    /// Games in which the opponent finished above the player
+    below_count: i64,
+    tie_count: i64,
+    avg_place: Option<f32>,
+    avg_opponent_place: Option<f32>,
+    /// Opponent's place minus the player's, averaged; positive when the
+    /// player usually finishes higher
+    avg_place_difference: Option<f32>,
+    /// Player's game points minus the opponent's, averaged
+    avg_game_point_difference: Option<f32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct HeadToHeadTournament {
+    tournament_id: i32,
+    tournament_name: String,
+    #[schema(value_type = String, format = Date)]
+    tournament_date: NaiveDate,
+    game_count: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct HeadToHead {
+    player: Player,
+    #[serde(flatten)]
+    #[schema(inline)]
+    record: OpponentRecord,
+    tournaments: Vec<HeadToHeadTournament>,
+    games: Vec<GameDetail>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct OpponentSummary {
+    player: Player,
+    /// Opponents the player finishes furthest above, best first
+    best: Vec<OpponentRecord>,
+    /// Opponents the player finishes furthest below, worst first
+    worst: Vec<OpponentRecord>,
+}
+
+#[derive(Deserialize, Debug, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct OpponentSummaryParams {
+    /// Only consider opponents met at least this many times (default 1)
+    min_games: Option<i64>,
+    /// Opponents listed in each of best and worst (default 3)
+    limit: Option<i64>,
+}
+
+async fn find_player(pool: &PgPool, player_id: i32) -> Result<Player, AppError> {
+    sqlx::query_as::<_, Player>("SELECT id, name FROM players WHERE id = $1")
+        .bind(player_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::not_found("player"))
+}
+
+/// Records of `player_id` against every opponent (or only `opponent_id`).
+async fn opponent_records(
+    pool: &PgPool,
+    player_id: i32,
+    opponent_id: Option<i32>,
+) -> Result<Vec<OpponentRecord>, AppError> {
+    // Table points run from 4 for 1st to 1 for 4th, so place = 5 - table_point
+    let records = sqlx::query_as::<_, OpponentRecord>(
+        r#"
+        SELECT
+            o.player_id as opponent_id,
+            p.name as opponent_name,
+            COUNT(*) as game_count,
+            COUNT(*) FILTER (WHERE me.table_point > o.table_point) as above_count,
+            COUNT(*) FILTER (WHERE me.table_point < o.table_point) as below_count,
+            COUNT(*) FILTER (WHERE me.table_point = o.table_point) as tie_count,
+            AVG(5 - me.table_point)::FLOAT4 as avg_place,
+            AVG(5 - o.table_point)::FLOAT4 as avg_opponent_place,
+            AVG(me.table_point - o.table_point)::FLOAT4 as avg_place_difference,
+            AVG(me.game_point - o.game_point)::FLOAT4 as avg_game_point_difference
+        FROM game_player_results me
+        JOIN game_player_results o ON o.game_id = me.game_id AND o.player_id <> me.player_id
+        JOIN players p ON p.id = o.player_id
+        WHERE me.player_id = $1
+          AND ($2::INTEGER IS NULL OR o.player_id = $2)
+        GROUP BY o.player_id, p.name
+        ORDER BY o.player_id
+        "#
+    )
+    .bind(player_id)
+    .bind(opponent_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(records)
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/players/{player_id}/vs/{opponent_id}",
+    params(
+        ("player_id" = i32, Path, description = "Player ID"),
+        ("opponent_id" = i32, Path, description = "Opponent's player ID")
+    ),
+    responses(
+        (status = 200, description = "Record between the two players and the games they shared", body = HeadToHead),
+        (status = 400, description = "Both IDs are the same player", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Player not found", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "Unexpected server error", body = Problem, content_type = "application/problem+json")
+    )
+)]
+pub async fn get_head_to_head(
+    State(pool): State<PgPool>,
+    Path((player_id, opponent_id)): Path<(i32, i32)>,
+) -> Result<Json<HeadToHead>, AppError> {
+    if player_id == opponent_id {
+        return Err(AppError::bad_request("same_player", "a player cannot be compared with themselves"));
+    }
+    let player = find_player(&pool, player_id).await?;
+    let opponent = find_player(&pool, opponent_id).await?;
+
+    let record = opponent_records(&pool, player_id, Some(opponent_id))
+        .await?
+        .pop()
+        .unwrap_or(OpponentRecord {
+            opponent_id: opponent.id,
+            opponent_name: opponent.name,
+            game_count: 0,
+            above_count: 0,
+            below_count: 0,
+            tie_count: 0,
+            avg_place: None,
+            avg_opponent_place: None,
+            avg_place_difference: None,
+            avg_game_point_difference: None,
+        });
+
+    let games = load_game_details(&pool, &[player_id, opponent_id]).await?;
+    let mut tournaments: Vec<HeadToHeadTournament> = Vec::new();
+    for game in &games {
+        match tournaments.iter_mut().find(|t| t.tournament_id == game.tournament_id) {
+            Some(tournament) => tournament.game_count += 1,
+            None => tournaments.push(HeadToHeadTournament {
+                tournament_id: game.tournament_id,
+                tournament_name: game.tournament_name.clone(),
+                tournament_date: game.tournament_date,
+                game_count: 1,
+            }),
+        }
+    }
+
+    Ok(Json(HeadToHead {
+        player,
+        record,
+        tournaments,
+        games,
+    }))
+}
+
+/// The `limit` opponents met at least `min_games` times with the highest and
+/// the lowest average place difference. Equal differences go to the opponent
+/// met more often, then to the lower ID, in both lists. With few opponents
+/// the two lists overlap.
+fn best_and_worst(
+    records: Vec<OpponentRecord>,
+    min_games: i64,
+    limit: usize,
+) -> (Vec<OpponentRecord>, Vec<OpponentRecord>) {
+    let mut records: Vec<OpponentRecord> = records.into_iter().filter(|r| r.game_count >= min_games).collect();
+    let difference = |r: &OpponentRecord| r.avg_place_difference.unwrap_or(0.0);
+    let by_evidence = |a: &OpponentRecord, b: &OpponentRecord| {
+        b.game_count.cmp(&a.game_count).then(a.opponent_id.cmp(&b.opponent_id))
+    };
+
+    records.sort_by(|a, b| difference(b).total_cmp(&difference(a)).then_with(|| by_evidence(a, b)));
+    let best = records.iter().take(limit).cloned().collect();
+    records.sort_by(|a, b| difference(a).total_cmp(&difference(b)).then_with(|| by_evidence(a, b)));
+    let worst = records.into_iter().take(limit).collect();
+    (best, worst)
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/players/{player_id}/opponents",
+    params(
+        ("player_id" = i32, Path, description = "Player ID"),
+        OpponentSummaryParams
+    ),
+    responses(
+        (status = 200, description = "The player's best and worst opponents by relative placement", body = OpponentSummary),
+        (status = 400, description = "Invalid min_games or limit", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Player not found", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "Unexpected server error", body = Problem, content_type = "application/problem+json")
+    )
+)]
+pub async fn get_opponent_summary(
+    State(pool): State<PgPool>,
+    Path(player_id): Path<i32>,
+    Query(params): Query<OpponentSummaryParams>,
+) -> Result<Json<OpponentSummary>, AppError> {
+    let min_games = params.min_games.unwrap_or(1);
+    if min_games < 1 {
+        return Err(AppError::bad_request("invalid_min_games", "min_games must be at least 1"));
+    }
+    let limit = params.limit.unwrap_or(3);
+    if limit < 1 {
+        return Err(AppError::bad_request("invalid_limit", "limit must be at least 1"));
+    }
+    let player = find_player(&pool, player_id).await?;
+
+    let records = opponent_records(&pool, player_id, None).await?;
+    let (best, worst) = best_and_worst(records, min_games, limit as usize);
+
+    Ok(Json(OpponentSummary { player, best, worst }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(opponent_id: i32, game_count: i64, avg_place_difference: f32) -> OpponentRecord {
+        OpponentRecord {
+            opponent_id,
+            opponent_name: format!("player {}", opponent_id),
+            game_count,
+            above_count: 0,
+            below_count: 0,
+            tie_count: 0,
+            avg_place: None,
+            avg_opponent_place: None,
+            avg_place_difference: Some(avg_place_difference),
+            avg_game_point_difference: None,
+        }
+    }
+
+    fn ids(records: &[OpponentRecord]) -> Vec<i32> {
+        records.iter().map(|r| r.opponent_id).collect()
+    }
+
+    #[test]
+    fn best_and_worst_are_ordered_by_place_difference() {
+        let records = vec![record(1, 4, 0.5), record(2, 4, -1.0), record(3, 4, 1.25), record(4, 4, 0.0)];
+        let (best, worst) = best_and_worst(records, 1, 2);
+        assert_eq!(ids(&best), [3, 1]);
+        assert_eq!(ids(&worst), [2, 4]);
+    }
+
+    #[test]
+    fn opponents_below_min_games_are_left_out() {
+        // Opponent 1 has the best and opponent 2 the worst record, from a single game each
+        let records = vec![record(1, 1, 3.0), record(2, 1, -3.0), record(3, 8, 0.5), record(4, 2, -0.5)];
+        let (best, worst) = best_and_worst(records, 2, 3);
+        assert_eq!(ids(&best), [3, 4]);
+        assert_eq!(ids(&worst), [4, 3]);
+    }
+
+    #[test]
+    fn equal_records_go_to_the_opponent_met_more_often_then_the_lower_id() {
+        let records = vec![record(5, 4, 1.0), record(2, 4, 1.0), record(7, 12, 1.0), record(3, 4, -1.0), record(9, 12, -1.0)];
+        let (best, worst) = best_and_worst(records, 1, 3);
+        assert_eq!(ids(&best), [7, 2, 5]);
+        assert_eq!(ids(&worst), [9, 3, 7]);
+    }
+
+    #[test]
+    fn lists_overlap_when_there_are_few_opponents() {
+        let (best, worst) = best_and_worst(vec![record(1, 4, 0.5), record(2, 4, -0.5)], 1, 3);
+        assert_eq!(ids(&best), [1, 2]);
+        assert_eq!(ids(&worst), [2, 1]);
+    }
+
+    #[test]
+    fn no_opponents_gives_empty_lists() {
+        let (best, worst) = best_and_worst(vec![record(1, 1, 0.0)], 2, 3);
+        assert!(best.is_empty());
+        assert!(worst.is_empty());
+    }
+}
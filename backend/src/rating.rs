@@ -0,0 +1,606 @@
+use axum::extract::State;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgPool;
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use utoipa::ToSchema;
+
+use crate::error::{AppError, Problem};
+use crate::extract::{Json, Path, Query};
+
+/// Rating of a player who has not played yet.
+pub const INITIAL_RATING: f64 = 1500.0;
+pub const INITIAL_DEVIATION: f64 = 350.0;
+pub const INITIAL_VOLATILITY: f64 = 0.06;
+/// Constrains how fast volatility can move; Glickman suggests 0.3 to 1.2.
+const TAU: f64 = 0.5;
+/// Converts between the Glicko scale and the Glicko-2 scale.
+const SCALE: f64 = 173.7178;
+const CONVERGENCE_TOLERANCE: f64 = 0.000001;
+
+/// A player's Glicko-2 rating on the Glicko scale.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rating {
+    pub rating: f64,
+    pub deviation: f64,
+    pub volatility: f64,
+}
+
+impl Default for Rating {
+    fn default() -> Self {
+        Rating {
+            rating: INITIAL_RATING,
+            deviation: INITIAL_DEVIATION,
+            volatility: INITIAL_VOLATILITY,
+        }
+    }
+}
+
+/// Result against one opponent: 1 for a win, 0.5 for a draw, 0 for a loss.
+pub struct Outcome {
+    pub opponent: Rating,
+    pub score: f64,
+}
+
+fn g(phi: f64) -> f64 {
+    1.0 / (1.0 + 3.0 * phi * phi / (PI * PI)).sqrt()
+}
+
+fn expected_score(mu: f64, opponent_mu: f64, opponent_phi: f64) -> f64 {
+    1.0 / (1.0 + (-g(opponent_phi) * (mu - opponent_mu)).exp())
+}
+
+/// New volatility, found with the Illinois algorithm (step 5 of Glickman's
+/// "Example of the Glicko-2 system").
+fn new_volatility(phi: f64, sigma: f64, v: f64, delta: f64) -> f64 {
+    let a = (sigma * sigma).ln();
+    let f = |x: f64| {
+        let ex = x.exp();
+        ex * (delta * delta - phi * phi - v - ex) / (2.0 * (phi * phi + v + ex).powi(2)) - (x - a) / (TAU * TAU)
+    };
+
+    let mut big_a = a;
+    let mut big_b = if delta * delta > phi * phi + v {
+        (delta * delta - phi * phi - v).ln()
+    } else {
+        let mut k = 1.0;
+        while f(a - k * TAU) < 0.0 {
+            k += 1.0;
+        }
+        a - k * TAU
+    };
+    let mut f_a = f(big_a);
+    let mut f_b = f(big_b);
+    while (big_b - big_a).abs() > CONVERGENCE_TOLERANCE {
+        let big_c = big_a + (big_a - big_b) * f_a / (f_b - f_a);
+        let f_c = f(big_c);
+        if f_c * f_b <= 0.0 {
+            big_a = big_b;
+            f_a = f_b;
+        } else {
+            f_a /= 2.0;
+        }
+        big_b = big_c;
+        f_b = f_c;
+    }
+    (big_a / 2.0).exp()
+}
+
+/// Applies one Glicko-2 rating period to `player`.
+pub fn update(player: Rating, outcomes: &[Outcome]) -> Rating {
+    let mu = (player.rating - INITIAL_RATING) / SCALE;
+    let phi = player.deviation / SCALE;
+    if outcomes.is_empty() {
+        let phi_star = (phi * phi + player.volatility * player.volatility).sqrt();
+        return Rating {
+            deviation: phi_star * SCALE,
+            ..player
+        };
+    }
+
+    let mut v_inverse = 0.0;
+    let mut improvement = 0.0;
+    for outcome in outcomes {
+        let opponent_mu = (outcome.opponent.rating - INITIAL_RATING) / SCALE;
+        let opponent_phi = outcome.opponent.deviation / SCALE;
+        let expected = expected_score(mu, opponent_mu, opponent_phi);
+        v_inverse += g(opponent_phi).powi(2) * expected * (1.0 - expected);
+        improvement += g(opponent_phi) * (outcome.score - expected);
+    }
+    let v = 1.0 / v_inverse;
+    let delta = v * improvement;
+
+    let sigma = new_volatility(phi, player.volatility, v, delta);
+    let phi_star = (phi * phi + sigma * sigma).sqrt();
+    let new_phi = 1.0 / (1.0 / (phi_star * phi_star) + 1.0 / v).sqrt();
+    let new_mu = mu + new_phi * new_phi * improvement;
+
+    Rating {
+        rating: new_mu * SCALE + INITIAL_RATING,
+        deviation: new_phi * SCALE,
+        volatility: sigma,
+    }
+}
+
+/// Rates one game as a rating period in which every player meets each other
+/// player once, scored by game points. All players are updated from their
+/// ratings before the game. Returns ratings in the order of `players`.
+pub fn rate_game(players: &[(Rating, i32)]) -> Vec<Rating> {
+    players
+        .iter()
+        .enumerate()
+        .map(|(index, &(rating, game_point))| {
+            let outcomes: Vec<Outcome> = players
+                .iter()
+                .enumerate()
+                .filter(|&(other, _)| other != index)
+                .map(|(_, &(opponent, opponent_point))| Outcome {
+                    opponent,
+                    score: match game_point.cmp(&opponent_point) {
+                        std::cmp::Ordering::Greater => 1.0,
+                        std::cmp::Ordering::Equal => 0.5,
+                        std::cmp::Ordering::Less => 0.0,
+                    },
+                })
+                .collect();
+            update(rating, &outcomes)
+        })
+        .collect()
+}
+
+/// Where a game falls in rating order: tournament date, tournament, round,
+/// then game. Games are rated in ascending order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, sqlx::FromRow)]
+pub struct RatingOrder {
+    pub date: NaiveDate,
+    pub tournament_id: i32,
+    pub round_number: i32,
+    pub game_id: i32,
+}
+
+/// Columns giving the rating order of a game, matching `RatingOrder`.
+const ORDER_COLUMNS: &str = "t.date, t.id, s.round_number, g.id";
+
+/// Games at or after the `RatingOrder` bound as `$1` to `$4`, or every game
+/// when those are NULL.
+const FROM_CONDITION: &str =
+    "($1::DATE IS NULL OR (t.date, t.id, s.round_number, g.id) >= ($1::DATE, $2::INTEGER, $3::INTEGER, $4::INTEGER))";
+
+async fn first_game(conn: &mut sqlx::PgConnection, condition: &str, id: i32) -> Result<Option<RatingOrder>, AppError> {
+    let query = format!(
+        r#"
+        SELECT t.date, t.id as tournament_id, s.round_number, g.id as game_id
+        FROM games g
+        JOIN sessions s ON s.id = g.session_id
+        JOIN tournaments t ON t.id = s.tournament_id
+        WHERE {} = $1
+        ORDER BY {}
+        LIMIT 1
+        "#,
+        condition, ORDER_COLUMNS
+    );
+    Ok(sqlx::query_as::<_, RatingOrder>(&query)
+        .bind(id)
+        .fetch_optional(&mut *conn)
+        .await?)
+}
+
+/// Rating order of a game, or `None` when it does not exist.
+pub async fn game_order(conn: &mut sqlx::PgConnection, game_id: i32) -> Result<Option<RatingOrder>, AppError> {
+    first_game(conn, "g.id", game_id).await
+}
+
+/// Rating order of the first game of a session, or `None` when it has none.
+pub async fn session_order(conn: &mut sqlx::PgConnection, session_id: i32) -> Result<Option<RatingOrder>, AppError> {
+    first_game(conn, "s.id", session_id).await
+}
+
+/// Rating order of the first game of a tournament, or `None` when it has
+/// none.
+pub async fn tournament_order(
+    conn: &mut sqlx::PgConnection,
+    tournament_id: i32,
+) -> Result<Option<RatingOrder>, AppError> {
+    first_game(conn, "t.id", tournament_id).await
+}
+
+/// Locks the ratings table until the transaction ends, so writes that
+/// replay ratings run one after another. Take it before reading the
+/// positions to replay from, or a concurrent write could move a game after
+/// its position was read.
+pub async fn lock_ratings(conn: &mut sqlx::PgConnection) -> Result<(), AppError> {
+    sqlx::query("LOCK TABLE player_ratings IN EXCLUSIVE MODE")
+        .execute(&mut *conn)
+        .await?;
+    Ok(())
+}
+
+/// The earlier of two positions, ignoring a missing one.
+pub fn earliest(a: Option<RatingOrder>, b: Option<RatingOrder>) -> Option<RatingOrder> {
+    a.into_iter().chain(b).min()
+}
+
+/// Replays every game in rating order and replaces all stored ratings.
+/// Returns the number of games rated.
+pub async fn rebuild_ratings(conn: &mut sqlx::PgConnection) -> Result<usize, AppError> {
+    replay(conn, None).await
+}
+
+/// Re-rates the games from `from` onwards after a write, starting from the
+/// ratings stored for the games before it. Pass the earliest position the
+/// write touched, before or after it, e.g. a game's old and new position
+/// when it moves. Returns the number of games rated.
+pub async fn replay_ratings_from(
+    conn: &mut sqlx::PgConnection,
+    from: Option<RatingOrder>,
+) -> Result<usize, AppError> {
+    match from {
+        Some(from) => replay(conn, Some(from)).await,
+        None => Ok(0),
+    }
+}
+
+/// Rates the games from `from` onwards, or all games.
+async fn replay(conn: &mut sqlx::PgConnection, from: Option<RatingOrder>) -> Result<usize, AppError> {
+    #[derive(sqlx::FromRow)]
+    struct ResultRow {
+        game_id: i32,
+        player_id: i32,
+        game_point: i32,
+    }
+
+    #[derive(sqlx::FromRow)]
+    struct StoredRating {
+        player_id: i32,
+        rating: f64,
+        deviation: f64,
+        volatility: f64,
+    }
+
+    lock_ratings(conn).await?;
+
+    let date = from.map(|from| from.date);
+    let tournament_id = from.map(|from| from.tournament_id);
+    let round_number = from.map(|from| from.round_number);
+    let game_id = from.map(|from| from.game_id);
+
+    sqlx::query(&format!(
+        r#"
+        DELETE FROM player_ratings pr
+        USING games g
+        JOIN sessions s ON s.id = g.session_id
+        JOIN tournaments t ON t.id = s.tournament_id
+        WHERE g.id = pr.game_id AND {}
+        "#,
+        FROM_CONDITION
+    ))
+    .bind(date)
+    .bind(tournament_id)
+    .bind(round_number)
+    .bind(game_id)
+    .execute(&mut *conn)
+    .await?;
+
+    // Everything left was rated before `from`, so each player's latest row
+    // is where they stand going into it
+    let mut current: HashMap<i32, Rating> = sqlx::query_as::<_, StoredRating>(
+        r#"
+        SELECT DISTINCT ON (pr.player_id) pr.player_id, pr.rating, pr.deviation, pr.volatility
+        FROM player_ratings pr
+        JOIN games g ON g.id = pr.game_id
+        JOIN sessions s ON s.id = g.session_id
+        JOIN tournaments t ON t.id = s.tournament_id
+        ORDER BY pr.player_id, t.date DESC, t.id DESC, s.round_number DESC, g.id DESC
+        "#
+    )
+    .fetch_all(&mut *conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        let rating = Rating {
+            rating: row.rating,
+            deviation: row.deviation,
+            volatility: row.volatility,
+        };
+        (row.player_id, rating)
+    })
+    .collect();
+
+    let rows = sqlx::query_as::<_, ResultRow>(&format!(
+        r#"
+        SELECT gpr.game_id, gpr.player_id, gpr.game_point
+        FROM game_player_results gpr
+        JOIN games g ON g.id = gpr.game_id
+        JOIN sessions s ON s.id = g.session_id
+        JOIN tournaments t ON t.id = s.tournament_id
+        WHERE {}
+        ORDER BY {}, gpr.id
+        "#,
+        FROM_CONDITION, ORDER_COLUMNS
+    ))
+    .bind(date)
+    .bind(tournament_id)
+    .bind(round_number)
+    .bind(game_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let mut game_ids = Vec::with_capacity(rows.len());
+    let mut player_ids = Vec::with_capacity(rows.len());
+    let mut ratings = Vec::with_capacity(rows.len());
+    let mut deviations = Vec::with_capacity(rows.len());
+    let mut volatilities = Vec::with_capacity(rows.len());
+    let mut changes = Vec::with_capacity(rows.len());
+    let mut game_count = 0;
+
+    for game in rows.chunk_by(|a, b| a.game_id == b.game_id) {
+        let before: Vec<(Rating, i32)> = game
+            .iter()
+            .map(|r| (current.get(&r.player_id).copied().unwrap_or_default(), r.game_point))
+            .collect();
+        let after = rate_game(&before);
+        for ((row, (old, _)), new) in game.iter().zip(&before).zip(after) {
+            game_ids.push(row.game_id);
+            player_ids.push(row.player_id);
+            ratings.push(new.rating);
+            deviations.push(new.deviation);
+            volatilities.push(new.volatility);
+            changes.push(new.rating - old.rating);
+            current.insert(row.player_id, new);
+        }
+        game_count += 1;
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO player_ratings (game_id, player_id, rating, deviation, volatility, rating_change)
+        SELECT * FROM UNNEST($1::INTEGER[], $2::INTEGER[], $3::FLOAT8[], $4::FLOAT8[], $5::FLOAT8[], $6::FLOAT8[])
+        "#
+    )
+    .bind(&game_ids)
+    .bind(&player_ids)
+    .bind(&ratings)
+    .bind(&deviations)
+    .bind(&volatilities)
+    .bind(&changes)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(game_count)
+}
+
+#[derive(Serialize, Deserialize, sqlx::FromRow, Debug, ToSchema)]
+pub struct RatingLeaderboardEntry {
+    player_id: i32,
+    player_name: String,
+    rating: f64,
+    deviation: f64,
+    volatility: f64,
+    /// `rating - 2 * deviation`: the rating the player is very likely to be
+    /// at least as good as. The leaderboard is ordered by it, so a few lucky
+    /// games with a wide deviation do not top it
+    conservative_rating: f64,
+    game_count: i64,
+    #[schema(value_type = String, format = Date)]
+    last_played: NaiveDate,
+}
+
+#[derive(Deserialize, Debug, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RatingLeaderboardParams {
+    /// Leave out players with fewer rated games than this
+    min_games: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, sqlx::FromRow, Debug, ToSchema)]
+pub struct RatingHistoryEntry {
+    game_id: i32,
+    tournament_id: i32,
+    tournament_name: String,
+    #[schema(value_type = String, format = Date)]
+    tournament_date: NaiveDate,
+    session_name: String,
+    /// Rating after the game
+    rating: f64,
+    deviation: f64,
+    volatility: f64,
+    rating_change: f64,
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/ratings",
+    params(RatingLeaderboardParams),
+    responses(
+        (status = 200, description = "Current rating of every rated player, by conservative rating", body = [RatingLeaderboardEntry]),
+        (status = 400, description = "Invalid query string", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "Unexpected server error", body = Problem, content_type = "application/problem+json")
+    )
+)]
+pub async fn get_rating_leaderboard(
+    State(pool): State<PgPool>,
+    Query(params): Query<RatingLeaderboardParams>,
+) -> Result<Json<Vec<RatingLeaderboardEntry>>, AppError> {
+    let leaderboard = sqlx::query_as::<_, RatingLeaderboardEntry>(
+        r#"
+        SELECT
+            latest.player_id,
+            p.name as player_name,
+            latest.rating,
+            latest.deviation,
+            latest.volatility,
+            latest.rating - 2 * latest.deviation as conservative_rating,
+            latest.game_count,
+            latest.last_played
+        FROM (
+            SELECT DISTINCT ON (pr.player_id)
+                pr.player_id,
+                pr.rating,
+                pr.deviation,
+                pr.volatility,
+                COUNT(*) OVER (PARTITION BY pr.player_id) as game_count,
+                t.date as last_played
+            FROM player_ratings pr
+            JOIN games g ON g.id = pr.game_id
+            JOIN sessions s ON s.id = g.session_id
+            JOIN tournaments t ON t.id = s.tournament_id
+            ORDER BY pr.player_id, t.date DESC, t.id DESC, s.round_number DESC, g.id DESC
+        ) latest
+        JOIN players p ON p.id = latest.player_id
+        WHERE latest.game_count >= COALESCE($1, 0)
+        ORDER BY conservative_rating DESC, latest.player_id
+        "#
+    )
+    .bind(params.min_games)
+    .fetch_all(&pool)
+    .await?;
+
+    Ok(Json(leaderboard))
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/players/{player_id}/ratings",
+    params(
+        ("player_id" = i32, Path, description = "Player ID")
+    ),
+    responses(
+        (status = 200, description = "The player's rating after each game, oldest first", body = [RatingHistoryEntry]),
+        (status = 400, description = "Invalid path parameter", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Player not found", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "Unexpected server error", body = Problem, content_type = "application/problem+json")
+    )
+)]
+pub async fn get_rating_history(
+    State(pool): State<PgPool>,
+    Path(player_id): Path<i32>,
+) -> Result<Json<Vec<RatingHistoryEntry>>, AppError> {
+    let exists = sqlx::query_scalar::<_, bool>("SELECT EXISTS (SELECT 1 FROM players WHERE id = $1)")
+        .bind(player_id)
+        .fetch_one(&pool)
+        .await?;
+    if !exists {
+        return Err(AppError::not_found("player"));
+    }
+
+    let history = sqlx::query_as::<_, RatingHistoryEntry>(
+        r#"
+        SELECT
+            pr.game_id,
+            t.id as tournament_id,
+            t.name as tournament_name,
+            t.date as tournament_date,
+            s.name as session_name,
+            pr.rating,
+            pr.deviation,
+            pr.volatility,
+            pr.rating_change
+        FROM player_ratings pr
+        JOIN games g ON g.id = pr.game_id
+        JOIN sessions s ON s.id = g.session_id
+        JOIN tournaments t ON t.id = s.tournament_id
+        WHERE pr.player_id = $1
+        ORDER BY t.date, t.id, s.round_number, g.id
+        "#
+    )
+    .bind(player_id)
+    .fetch_all(&pool)
+    .await?;
+
+    Ok(Json(history))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rating(rating: f64, deviation: f64) -> Rating {
+        Rating {
+            rating,
+            deviation,
+            volatility: INITIAL_VOLATILITY,
+        }
+    }
+
+    fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+        assert!((actual - expected).abs() < tolerance, "{} is not within {} of {}", actual, tolerance, expected);
+    }
+
+    /// The worked example in Glickman's "Example of the Glicko-2 system".
+    #[test]
+    fn update_matches_glickmans_example() {
+        let outcomes = [
+            Outcome {
+                opponent: rating(1400.0, 30.0),
+                score: 1.0,
+            },
+            Outcome {
+                opponent: rating(1550.0, 100.0),
+                score: 0.0,
+            },
+            Outcome {
+                opponent: rating(1700.0, 300.0),
+                score: 0.0,
+            },
+        ];
+        let updated = update(rating(1500.0, 200.0), &outcomes);
+        assert_close(updated.rating, 1464.06, 0.01);
+        assert_close(updated.deviation, 151.52, 0.01);
+        assert_close(updated.volatility, 0.05999, 0.00001);
+    }
+
+    #[test]
+    fn a_period_without_games_only_widens_the_deviation() {
+        let player = rating(1500.0, 200.0);
+        let updated = update(player, &[]);
+        assert_eq!(updated.rating, player.rating);
+        assert_eq!(updated.volatility, player.volatility);
+        assert_close(updated.deviation, (200.0f64.powi(2) + (0.06 * SCALE).powi(2)).sqrt(), 1e-9);
+    }
+
+    #[test]
+    fn rate_game_scores_each_pair_by_game_points() {
+        let after = rate_game(&[
+            (Rating::default(), 300),
+            (Rating::default(), 100),
+            (Rating::default(), -100),
+            (Rating::default(), -300),
+        ]);
+        assert!(after[0].rating > after[1].rating);
+        assert!(after[1].rating > INITIAL_RATING);
+        assert!(after[2].rating < INITIAL_RATING);
+        assert!(after[3].rating < after[2].rating);
+        assert_close(after[0].rating - INITIAL_RATING, INITIAL_RATING - after[3].rating, 1e-9);
+    }
+
+    #[test]
+    fn rate_game_scores_tied_players_as_draws() {
+        let after = rate_game(&[
+            (Rating::default(), 200),
+            (Rating::default(), 0),
+            (Rating::default(), 0),
+            (Rating::default(), -200),
+        ]);
+        // Each tied player beat one, lost to one and drew with the other
+        assert_eq!(after[1], after[2]);
+        assert_close(after[1].rating, INITIAL_RATING, 1e-9);
+        assert!(after[1].deviation < INITIAL_DEVIATION);
+
+        let all_tied = rate_game(&[(Rating::default(), 0); 4]);
+        assert!(all_tied.iter().all(|r| (r.rating - INITIAL_RATING).abs() < 1e-9));
+    }
+
+    #[test]
+    fn earliest_ignores_missing_positions() {
+        let order = |game_id| RatingOrder {
+            date: NaiveDate::from_ymd_opt(2024, 4, 1).unwrap(),
+            tournament_id: 1,
+            round_number: 1,
+            game_id,
+        };
+        assert_eq!(earliest(Some(order(2)), Some(order(1))), Some(order(1)));
+        assert_eq!(earliest(None, Some(order(2))), Some(order(2)));
+        assert_eq!(earliest(None, None), None);
+    }
+}
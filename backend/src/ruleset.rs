@@ -0,0 +1,470 @@
+use axum::{extract::State, http::StatusCode};
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgPool;
+use utoipa::ToSchema;
+
+use crate::auth::Admin;
+use crate::error::{AppError, Problem};
+use crate::extract::Json;
+use crate::scoring;
+use crate::validation::{ValidationErrors, PLAYERS_PER_GAME};
+
+/// Columns selected for every `Ruleset` query.
+const RULESET_COLUMNS: &str = "id, name, starting_points, return_points, uma, tie_handling, kiriage_mangan";
+
+/// Points (not game points, which are in hundreds) per place point. Uma and
+/// oka are both in place points, so uma 3/1/-1/-3 is a 30,000/10,000 spread.
+pub const POINTS_PER_PLACE_POINT: i32 = 10000;
+
+/// How players finishing on the same score are placed.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TieHandling {
+    /// Tied players share the positions they span and split their points
+    #[default]
+    Split,
+    /// The player listed first in the game (closer to the first dealer)
+    /// takes the higher position
+    SeatOrder,
+}
+
+impl TieHandling {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TieHandling::Split => "split",
+            TieHandling::SeatOrder => "seat_order",
+        }
+    }
+
+    fn from_db(value: &str) -> Option<TieHandling> {
+        match value {
+            "split" => Some(TieHandling::Split),
+            "seat_order" => Some(TieHandling::SeatOrder),
+            _ => None,
+        }
+    }
+}
+
+/// Scoring rules a tournament is played under. Place points are the uma for
+/// the finishing position, plus the oka for first place, in units of
+/// [`POINTS_PER_PLACE_POINT`]; starting and return points are plain points.
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct Ruleset {
+    pub id: i32,
+    pub name: String,
+    /// Points each player starts with, e.g. 25000
+    pub starting_points: i32,
+    /// Points each player is measured against, e.g. 30000
+    pub return_points: i32,
+    /// Place points for 1st to 4th
+    pub uma: Vec<f32>,
+    pub tie_handling: TieHandling,
+    /// Round 4 han 30 fu and 3 han 60 fu up to a mangan
+    pub kiriage_mangan: bool,
+}
+
+impl Ruleset {
+    /// Bonus to first place, in place points: what every player pays in by
+    /// returning more than they started with. 25000/30000 gives 2.
+    pub fn oka(&self) -> f32 {
+        (self.return_points - self.starting_points) as f32 * PLAYERS_PER_GAME as f32 / POINTS_PER_PLACE_POINT as f32
+    }
+
+    /// Place points for each finishing position, oka included.
+    pub fn place_points_by_rank(&self) -> Vec<f32> {
+        let oka = self.oka();
+        self.uma
+            .iter()
+            .enumerate()
+            .map(|(rank, uma)| if rank == 0 { uma + oka } else { *uma })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+impl Ruleset {
+    /// The seeded 'standard' ruleset: no oka, uma 3/1/-1/-3, ties split.
+    pub fn standard() -> Ruleset {
+        Ruleset {
+            id: 1,
+            name: "standard".to_string(),
+            starting_points: 25000,
+            return_points: 25000,
+            uma: vec![3.0, 1.0, -1.0, -3.0],
+            tie_handling: TieHandling::Split,
+            kiriage_mangan: false,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct RulesetRow {
+    id: i32,
+    name: String,
+    starting_points: i32,
+    return_points: i32,
+    uma: Vec<f32>,
+    tie_handling: String,
+    kiriage_mangan: bool,
+}
+
+impl RulesetRow {
+    fn into_ruleset(self) -> Result<Ruleset, AppError> {
+        let tie_handling = TieHandling::from_db(&self.tie_handling)
+            .ok_or_else(|| AppError::internal(format!("ruleset {} has an unknown tie handling", self.id)))?;
+        Ok(Ruleset {
+            id: self.id,
+            name: self.name,
+            starting_points: self.starting_points,
+            return_points: self.return_points,
+            uma: self.uma,
+            tie_handling,
+            kiriage_mangan: self.kiriage_mangan,
+        })
+    }
+}
+
+/// The scoring rules of a ruleset without its identity, as submitted by
+/// clients.
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct RulesetDefinition {
+    #[serde(default = "default_starting_points")]
+    pub starting_points: i32,
+    /// Defaults to `starting_points`, i.e. no oka
+    pub return_points: Option<i32>,
+    /// Place points for 1st to 4th
+    pub uma: Vec<f32>,
+    #[serde(default)]
+    pub tie_handling: TieHandling,
+    #[serde(default)]
+    pub kiriage_mangan: bool,
+}
+
+fn default_starting_points() -> i32 {
+    25000
+}
+
+impl RulesetDefinition {
+    /// Checks the rules, reporting violations under `prefix` (e.g. `ruleset.`).
+    /// Values on the wrong scale, such as uma in thousands of points or
+    /// starting points in hundreds, are rejected rather than producing place
+    /// points ten or a hundred times too large.
+    pub fn validate(&self, errors: &mut ValidationErrors, prefix: &str) {
+        for (field, points) in [("starting_points", self.starting_points), ("return_points", self.return_points())] {
+            if points <= 0 {
+                errors.push(
+                    format!("{}{}", prefix, field),
+                    "not_positive",
+                    format!("{} must be greater than 0", field),
+                );
+            } else if points % 100 != 0 {
+                errors.push(
+                    format!("{}{}", prefix, field),
+                    "not_whole_hundreds",
+                    format!("{} must be in whole hundreds of points, got {}", field, points),
+                );
+            }
+        }
+        if self.uma.len() != PLAYERS_PER_GAME {
+            errors.push(
+                format!("{}uma", prefix),
+                "uma_length",
+                format!("uma needs one value per position ({}), got {}", PLAYERS_PER_GAME, self.uma.len()),
+            );
+        }
+        if self.uma.iter().sum::<f32>().abs() > 1e-3 {
+            errors.push(format!("{}uma", prefix), "uma_sum", "uma must sum to 0");
+        }
+
+        // No place point can be worth more than every point on the table
+        if self.starting_points > 0 {
+            let table_total = self.starting_points as f32 * PLAYERS_PER_GAME as f32 / POINTS_PER_PLACE_POINT as f32;
+            let ruleset = self.unsaved("");
+            if ruleset
+                .place_points_by_rank()
+                .iter()
+                .any(|place_point| place_point.abs() > table_total)
+            {
+                errors.push(
+                    format!("{}uma", prefix),
+                    "place_point_scale",
+                    format!(
+                        "uma and oka are in units of {} points and may not exceed the {} on the table",
+                        POINTS_PER_PLACE_POINT, table_total
+                    ),
+                );
+            }
+        }
+    }
+
+    pub fn return_points(&self) -> i32 {
+        self.return_points.unwrap_or(self.starting_points)
+    }
+
+    /// A ruleset that exists only for this request and is never stored.
+    pub fn unsaved(&self, name: &str) -> Ruleset {
+        Ruleset {
+            id: 0,
+            name: name.to_string(),
+            starting_points: self.starting_points,
+            return_points: self.return_points(),
+            uma: self.uma.clone(),
+            tie_handling: self.tie_handling,
+            kiriage_mangan: self.kiriage_mangan,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct CreateRulesetRequest {
+    name: String,
+    #[serde(flatten)]
+    rules: RulesetDefinition,
+}
+
+/// Loads the ruleset a tournament is played under, or `None` when the
+/// tournament does not exist.
+pub async fn ruleset_for_tournament(
+    conn: &mut sqlx::PgConnection,
+    tournament_id: i32,
+) -> Result<Option<Ruleset>, AppError> {
+    let query = format!(
+        "SELECT {} FROM rulesets WHERE id = (SELECT ruleset_id FROM tournaments WHERE id = $1)",
+        RULESET_COLUMNS
+    );
+    sqlx::query_as::<_, RulesetRow>(&query)
+        .bind(tournament_id)
+        .fetch_optional(&mut *conn)
+        .await?
+        .map(RulesetRow::into_ruleset)
+        .transpose()
+}
+
+#[derive(sqlx::FromRow)]
+struct StoredResult {
+    id: i32,
+    game_id: i32,
+    game_point: i32,
+    table_point: f32,
+}
+
+/// Games, from `rows` ordered by game, whose stored table points are not the
+/// ones `ruleset` derives from their game points.
+fn games_disagreeing_with(ruleset: &Ruleset, rows: &[StoredResult]) -> Vec<i32> {
+    rows.chunk_by(|a, b| a.game_id == b.game_id)
+        .filter(|game| {
+            let game_points: Vec<i32> = game.iter().map(|r| r.game_point).collect();
+            let derived = scoring::derive_points(ruleset, &game_points);
+            game.iter().zip(derived).any(|(row, points)| row.table_point != points.table_point)
+        })
+        .map(|game| game[0].game_id)
+        .collect()
+}
+
+/// Recomputes the stored table and place points of every game in a
+/// tournament, after the tournament moved from `previous` to `ruleset`.
+/// Table points only change where the new tie handling places tied players
+/// differently. Games whose stored table points do not follow their game
+/// points under `previous` are reported with a conflict rather than
+/// overwritten, as they need correcting from the score sheet first.
+pub async fn rederive_tournament_points(
+    conn: &mut sqlx::PgConnection,
+    tournament_id: i32,
+    previous: &Ruleset,
+    ruleset: &Ruleset,
+) -> Result<(), AppError> {
+    let rows = sqlx::query_as::<_, StoredResult>(
+        r#"
+        SELECT gpr.id, gpr.game_id, gpr.game_point, gpr.table_point
+        FROM game_player_results gpr
+        JOIN games g ON gpr.game_id = g.id
+        JOIN sessions s ON g.session_id = s.id
+        WHERE s.tournament_id = $1
+        ORDER BY gpr.game_id, gpr.id
+        "#
+    )
+    .bind(tournament_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let disagreeing = games_disagreeing_with(previous, &rows);
+    if !disagreeing.is_empty() {
+        let ids: Vec<String> = disagreeing.iter().map(i32::to_string).collect();
+        return Err(AppError::conflict(
+            "inconsistent_table_points",
+            format!(
+                "games {} have table points that do not follow their game points; correct them before changing the ruleset",
+                ids.join(", ")
+            ),
+        ));
+    }
+
+    for game in rows.chunk_by(|a, b| a.game_id == b.game_id) {
+        let game_points: Vec<i32> = game.iter().map(|r| r.game_point).collect();
+        let derived = scoring::derive_points(ruleset, &game_points);
+        for (row, points) in game.iter().zip(derived) {
+            sqlx::query("UPDATE game_player_results SET table_point = $2, place_point = $3 WHERE id = $1")
+                .bind(row.id)
+                .bind(points.table_point)
+                .bind(points.place_point)
+                .execute(&mut *conn)
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/rulesets",
+    responses(
+        (status = 200, description = "Ruleset list", body = [Ruleset]),
+        (status = 500, description = "Unexpected server error", body = Problem, content_type = "application/problem+json")
+    )
+)]
+pub async fn get_rulesets(State(pool): State<PgPool>) -> Result<Json<Vec<Ruleset>>, AppError> {
+    let query = format!("SELECT {} FROM rulesets ORDER BY id", RULESET_COLUMNS);
+    let rows = sqlx::query_as::<_, RulesetRow>(&query)
+        .fetch_all(&pool)
+        .await?;
+
+    let rulesets = rows
+        .into_iter()
+        .map(RulesetRow::into_ruleset)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Json(rulesets))
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/rulesets",
+    request_body = CreateRulesetRequest,
+    responses(
+        (status = 201, description = "Ruleset created", body = Ruleset),
+        (status = 400, description = "Malformed request body", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Missing or invalid token", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Admin role required", body = Problem, content_type = "application/problem+json"),
+        (status = 409, description = "Ruleset name already taken", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "Ruleset failed validation", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "Unexpected server error", body = Problem, content_type = "application/problem+json")
+    ),
+    security(("bearer" = []))
+)]
+pub async fn create_ruleset(
+    _: Admin,
+    State(pool): State<PgPool>,
+    Json(request): Json<CreateRulesetRequest>,
+) -> Result<(StatusCode, Json<Ruleset>), AppError> {
+    let mut errors = ValidationErrors::default();
+    let name = request.name.trim().to_string();
+    if name.is_empty() {
+        errors.push("name", "empty_name", "ruleset name must not be empty");
+    }
+    request.rules.validate(&mut errors, "");
+    errors.into_result(())?;
+
+    // Rulesets are never edited once created, so stored points never go stale
+    let query = format!(
+        "INSERT INTO rulesets (name, starting_points, return_points, uma, tie_handling, kiriage_mangan) VALUES ($1, $2, $3, $4, $5, $6) RETURNING {}",
+        RULESET_COLUMNS
+    );
+    let ruleset = sqlx::query_as::<_, RulesetRow>(&query)
+        .bind(&name)
+        .bind(request.rules.starting_points)
+        .bind(request.rules.return_points())
+        .bind(&request.rules.uma)
+        .bind(request.rules.tie_handling.as_str())
+        .bind(request.rules.kiriage_mangan)
+        .fetch_one(&pool)
+        .await?
+        .into_ruleset()?;
+
+    Ok((StatusCode::CREATED, Json(ruleset)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn definition(starting_points: i32, return_points: i32, uma: &[f32]) -> RulesetDefinition {
+        RulesetDefinition {
+            starting_points,
+            return_points: Some(return_points),
+            uma: uma.to_vec(),
+            tie_handling: TieHandling::Split,
+            kiriage_mangan: false,
+        }
+    }
+
+    fn codes(definition: &RulesetDefinition) -> Vec<String> {
+        let mut errors = ValidationErrors::default();
+        definition.validate(&mut errors, "");
+        errors.violations.into_iter().map(|v| v.code).collect()
+    }
+
+    #[test]
+    fn oka_is_in_place_points() {
+        let ruleset = definition(25000, 30000, &[3.0, 1.0, -1.0, -3.0]).unsaved("m-league");
+        assert_eq!(ruleset.oka(), 2.0);
+        assert_eq!(ruleset.place_points_by_rank(), [5.0, 1.0, -1.0, -3.0]);
+        assert_eq!(Ruleset::standard().oka(), 0.0);
+    }
+
+    #[test]
+    fn accepts_rules_on_the_place_point_scale() {
+        assert!(codes(&definition(25000, 25000, &[3.0, 1.0, -1.0, -3.0])).is_empty());
+        assert!(codes(&definition(25000, 30000, &[3.0, 1.0, -1.0, -3.0])).is_empty());
+        assert!(codes(&definition(30000, 30000, &[1.5, 0.5, -0.5, -1.5])).is_empty());
+    }
+
+    #[test]
+    fn rejects_uma_in_thousands_or_points() {
+        assert_eq!(codes(&definition(25000, 30000, &[30.0, 10.0, -10.0, -30.0])), ["place_point_scale"]);
+        assert_eq!(codes(&definition(25000, 25000, &[30000.0, 10000.0, -10000.0, -30000.0])), ["place_point_scale"]);
+    }
+
+    #[test]
+    fn rejects_points_in_hundreds_or_thousands() {
+        assert_eq!(codes(&definition(300, 300, &[3.0, 1.0, -1.0, -3.0])), ["place_point_scale"]);
+        assert_eq!(
+            codes(&definition(25, 30, &[3.0, 1.0, -1.0, -3.0])),
+            ["not_whole_hundreds", "not_whole_hundreds", "place_point_scale"]
+        );
+        assert_eq!(codes(&definition(25000, 30, &[3.0, 1.0, -1.0, -3.0])), ["not_whole_hundreds"]);
+    }
+
+    #[test]
+    fn rejects_uma_that_does_not_balance() {
+        assert_eq!(codes(&definition(25000, 25000, &[3.0, 1.0, -1.0, -2.0])), ["uma_sum"]);
+        assert_eq!(codes(&definition(25000, 25000, &[3.0, -3.0])), ["uma_length"]);
+    }
+
+    #[test]
+    fn finds_games_whose_table_points_disagree_with_their_game_points() {
+        let row = |game_id, game_point, table_point| StoredResult {
+            id: 0,
+            game_id,
+            game_point,
+            table_point,
+        };
+        let rows = [
+            // Seed game 6: the top and bottom scores have swapped table points
+            row(6, -7, 2.0),
+            row(6, 57, 3.0),
+            row(6, 109, 1.0),
+            row(6, -159, 4.0),
+            row(7, 200, 4.0),
+            row(7, 0, 2.5),
+            row(7, 0, 2.5),
+            row(7, -200, 1.0),
+        ];
+        assert_eq!(games_disagreeing_with(&Ruleset::standard(), &rows), [6]);
+
+        let seat_order = Ruleset {
+            tie_handling: TieHandling::SeatOrder,
+            ..Ruleset::standard()
+        };
+        assert_eq!(games_disagreeing_with(&seat_order, &rows), [6, 7]);
+    }
+}
@@ -0,0 +1,108 @@
+use crate::ruleset::{Ruleset, TieHandling};
+
+/// Table points awarded by finishing position: 1st=4, 2nd=3, 3rd=2, 4th=1.
+pub const TABLE_POINTS_BY_RANK: [f32; 4] = [4.0, 3.0, 2.0, 1.0];
+
+/// Points derived from a player's final game points under a ruleset.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DerivedPoints {
+    pub table_point: f32,
+    pub place_point: f32,
+}
+
+/// Places each player by final game points, returning for every input index
+/// the first position it occupies and how many players share it. Under
+/// `Split` players on the same score share the positions they span; under
+/// `SeatOrder` the player listed first (closer to the first dealer) ranks
+/// higher, so nobody shares a position.
+fn placements(game_points: &[i32], tie_handling: TieHandling) -> Vec<(usize, usize)> {
+    let mut order: Vec<usize> = (0..game_points.len()).collect();
+    // Stable sort, so equal scores keep seat order
+    order.sort_by(|&a, &b| game_points[b].cmp(&game_points[a]));
+
+    let mut result = vec![(0, 1); game_points.len()];
+    let mut i = 0;
+    while i < order.len() {
+        let score = game_points[order[i]];
+        let tied = match tie_handling {
+            TieHandling::Split => order[i..]
+                .iter()
+                .take_while(|&&index| game_points[index] == score)
+                .count(),
+            TieHandling::SeatOrder => 1,
+        };
+
+        for &index in &order[i..i + tied] {
+            result[index] = (i, tied);
+        }
+        i += tied;
+    }
+
+    result
+}
+
+/// Averages `by_rank` over the positions `first..first + tied`.
+fn shared_average(by_rank: &[f32], first: usize, tied: usize) -> f32 {
+    let total: f32 = (first..first + tied)
+        .map(|rank| by_rank.get(rank).copied().unwrap_or(0.0))
+        .sum();
+    total / tied as f32
+}
+
+/// Derives each player's table and place points from the final game points,
+/// in the same order as the input. Players sharing positions receive the
+/// average of those positions' points, e.g. a tie for 2nd gives both
+/// players (3 + 2) / 2 = 2.5 table points.
+///
+/// Mirrors `calculateTablePoints` in the score entry page for table points.
+pub fn derive_points(ruleset: &Ruleset, game_points: &[i32]) -> Vec<DerivedPoints> {
+    let place_points = ruleset.place_points_by_rank();
+    placements(game_points, ruleset.tie_handling)
+        .into_iter()
+        .map(|(first, tied)| DerivedPoints {
+            table_point: shared_average(&TABLE_POINTS_BY_RANK, first, tied),
+            place_point: shared_average(&place_points, first, tied),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table_points(game_points: &[i32]) -> Vec<f32> {
+        derive_points(&Ruleset::standard(), game_points).iter().map(|p| p.table_point).collect()
+    }
+
+    #[test]
+    fn ranks_by_game_points_in_input_order() {
+        assert_eq!(table_points(&[-7, 57, 109, -159]), [2.0, 3.0, 4.0, 1.0]);
+        assert_eq!(table_points(&[300, 100, -100, -300]), [4.0, 3.0, 2.0, 1.0]);
+    }
+
+    #[test]
+    fn place_points_are_the_uma_of_each_position() {
+        let place_points: Vec<f32> =
+            derive_points(&Ruleset::standard(), &[-7, 57, 109, -159]).iter().map(|p| p.place_point).collect();
+        assert_eq!(place_points, [-1.0, 1.0, 3.0, -3.0]);
+    }
+
+    #[test]
+    fn tied_players_split_the_positions_they_span() {
+        assert_eq!(table_points(&[200, 0, 0, -200]), [4.0, 2.5, 2.5, 1.0]);
+        assert_eq!(table_points(&[100, 100, -100, -100]), [3.5, 3.5, 1.5, 1.5]);
+        assert_eq!(table_points(&[0, 0, 0, 0]), [2.5, 2.5, 2.5, 2.5]);
+    }
+
+    #[test]
+    fn tied_players_split_place_points() {
+        let place_points: Vec<f32> =
+            derive_points(&Ruleset::standard(), &[100, 100, 0, -200]).iter().map(|p| p.place_point).collect();
+        assert_eq!(place_points, [2.0, 2.0, -1.0, -3.0]);
+    }
+
+    #[test]
+    fn placements_report_first_position_and_tie_size() {
+        assert_eq!(placements(&[0, 300, 0, -300], TieHandling::Split), [(1, 2), (0, 1), (1, 2), (3, 1)]);
+    }
+}
@@ -0,0 +1,602 @@
+use axum::{extract::State, http::StatusCode};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgPool;
+use std::collections::HashMap;
+use utoipa::ToSchema;
+
+use crate::auth::{Admin, RequireRole, Scorekeeper};
+use crate::error::{AppError, Problem};
+use crate::extract::{Json, Path};
+use crate::validation::ValidationErrors;
+use crate::{load_tournament_details, Tournament};
+
+/// Columns selected for every `Season` query, with `s` aliasing `seasons`.
+const SEASON_COLUMNS: &str = r#"
+    s.id, s.name, s.scoring, s.league_points, s.drop_worst, s.min_attendance,
+    ARRAY(
+        SELECT st.tournament_id
+        FROM season_tournaments st
+        JOIN tournaments t ON t.id = st.tournament_id
+        WHERE st.season_id = s.id
+        ORDER BY t.date, t.id
+    ) AS tournament_ids
+"#;
+
+/// What a player earns from each tournament of a season.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SeasonScoring {
+    /// The player's total table points in the tournament
+    #[default]
+    TablePoints,
+    /// `league_points` by final tournament place
+    LeaguePoints,
+}
+
+impl SeasonScoring {
+    fn as_str(self) -> &'static str {
+        match self {
+            SeasonScoring::TablePoints => "table_points",
+            SeasonScoring::LeaguePoints => "league_points",
+        }
+    }
+
+    fn from_db(value: &str) -> Option<SeasonScoring> {
+        match value {
+            "table_points" => Some(SeasonScoring::TablePoints),
+            "league_points" => Some(SeasonScoring::LeaguePoints),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct Season {
+    id: i32,
+    name: String,
+    scoring: SeasonScoring,
+    /// Points for 1st, 2nd, ... place under `league_points` scoring; places
+    /// past the end earn nothing
+    league_points: Vec<f32>,
+    /// Each player's lowest-scoring tournaments left out of their total;
+    /// tournaments they missed are the first to go
+    drop_worst: i32,
+    /// Tournaments a player must attend to be ranked
+    min_attendance: i32,
+    /// In date order
+    tournament_ids: Vec<i32>,
+}
+
+#[derive(sqlx::FromRow)]
+struct SeasonRow {
+    id: i32,
+    name: String,
+    scoring: String,
+    league_points: Vec<f32>,
+    drop_worst: i32,
+    min_attendance: i32,
+    tournament_ids: Vec<i32>,
+}
+
+impl SeasonRow {
+    fn into_season(self) -> Result<Season, AppError> {
+        let scoring = SeasonScoring::from_db(&self.scoring)
+            .ok_or_else(|| AppError::internal(format!("season {} has an unknown scoring", self.id)))?;
+        Ok(Season {
+            id: self.id,
+            name: self.name,
+            scoring,
+            league_points: self.league_points,
+            drop_worst: self.drop_worst,
+            min_attendance: self.min_attendance,
+            tournament_ids: self.tournament_ids,
+        })
+    }
+}
+
+impl Season {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+        if self.name.trim().is_empty() {
+            errors.push("name", "empty_name", "season name must not be empty");
+        }
+        if self.scoring == SeasonScoring::LeaguePoints && self.league_points.is_empty() {
+            errors.push("league_points", "missing_league_points", "league_points scoring needs league_points");
+        }
+        if self.league_points.iter().any(|points| *points < 0.0) {
+            errors.push("league_points", "negative_league_points", "league points must not be negative");
+        }
+        if self.drop_worst < 0 {
+            errors.push("drop_worst", "negative", "drop_worst must not be negative");
+        }
+        if self.min_attendance < 0 {
+            errors.push("min_attendance", "negative", "min_attendance must not be negative");
+        }
+        errors.into_result(())
+    }
+
+    /// Points a tournament result earns under this season's scoring.
+    fn score(&self, tournament_place: i32, table_point: f32) -> f32 {
+        match self.scoring {
+            SeasonScoring::TablePoints => table_point,
+            SeasonScoring::LeaguePoints => usize::try_from(tournament_place - 1)
+                .ok()
+                .and_then(|index| self.league_points.get(index))
+                .copied()
+                .unwrap_or(0.0),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct CreateSeasonRequest {
+    name: String,
+    #[serde(default)]
+    scoring: SeasonScoring,
+    #[serde(default)]
+    league_points: Vec<f32>,
+    #[serde(default)]
+    drop_worst: i32,
+    #[serde(default)]
+    min_attendance: i32,
+    #[serde(default)]
+    tournament_ids: Vec<i32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct UpdateSeasonRequest {
+    name: Option<String>,
+    scoring: Option<SeasonScoring>,
+    league_points: Option<Vec<f32>>,
+    drop_worst: Option<i32>,
+    min_attendance: Option<i32>,
+    /// Replaces the season's tournaments
+    tournament_ids: Option<Vec<i32>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct SeasonTournamentResult {
+    tournament_id: i32,
+    tournament_name: String,
+    #[schema(value_type = String, format = Date)]
+    tournament_date: NaiveDate,
+    tournament_place: i32,
+    table_point: f32,
+    game_point: i32,
+    /// What the result earns under the season's scoring
+    points: f32,
+    /// Left out of the total by `drop_worst`
+    dropped: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct SeasonStanding {
+    /// `None` for players below the minimum attendance
+    place: Option<i32>,
+    player_id: i32,
+    player_name: String,
+    attendance: i32,
+    /// Points from the results that count
+    total_points: f32,
+    /// Game points from the results that count; breaks ties in `total_points`
+    total_game_point: i32,
+    /// In date order
+    results: Vec<SeasonTournamentResult>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct SeasonStandings {
+    season: Season,
+    /// Ranked players first, then unranked ones by points
+    standings: Vec<SeasonStanding>,
+}
+
+async fn fetch_season(conn: &mut sqlx::PgConnection, season_id: i32) -> Result<Season, AppError> {
+    let query = format!("SELECT {} FROM seasons s WHERE s.id = $1", SEASON_COLUMNS);
+    sqlx::query_as::<_, SeasonRow>(&query)
+        .bind(season_id)
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or_else(|| AppError::not_found("season"))?
+        .into_season()
+}
+
+async fn set_season_tournaments(
+    conn: &mut sqlx::PgConnection,
+    season_id: i32,
+    tournament_ids: &[i32],
+) -> Result<(), AppError> {
+    sqlx::query("DELETE FROM season_tournaments WHERE season_id = $1")
+        .bind(season_id)
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query(
+        "INSERT INTO season_tournaments (season_id, tournament_id) SELECT $1, UNNEST($2::INTEGER[]) ON CONFLICT DO NOTHING"
+    )
+    .bind(season_id)
+    .bind(tournament_ids)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/seasons",
+    responses(
+        (status = 200, description = "Season list", body = [Season]),
+        (status = 500, description = "Unexpected server error", body = Problem, content_type = "application/problem+json")
+    )
+)]
+pub async fn get_seasons(State(pool): State<PgPool>) -> Result<Json<Vec<Season>>, AppError> {
+    let query = format!("SELECT {} FROM seasons s ORDER BY s.id", SEASON_COLUMNS);
+    let rows = sqlx::query_as::<_, SeasonRow>(&query)
+        .fetch_all(&pool)
+        .await?;
+
+    let seasons = rows
+        .into_iter()
+        .map(SeasonRow::into_season)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Json(seasons))
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/seasons",
+    request_body = CreateSeasonRequest,
+    responses(
+        (status = 201, description = "Season created", body = Season),
+        (status = 400, description = "Malformed request body", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Missing or invalid token", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Scorekeeper role required", body = Problem, content_type = "application/problem+json"),
+        (status = 409, description = "Season name already taken", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "Season failed validation or references an unknown tournament", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "Unexpected server error", body = Problem, content_type = "application/problem+json")
+    ),
+    security(("bearer" = []))
+)]
+pub async fn create_season(
+    RequireRole(user): Scorekeeper,
+    State(pool): State<PgPool>,
+    Json(request): Json<CreateSeasonRequest>,
+) -> Result<(StatusCode, Json<Season>), AppError> {
+    let season = Season {
+        id: 0,
+        name: request.name.trim().to_string(),
+        scoring: request.scoring,
+        league_points: request.league_points,
+        drop_worst: request.drop_worst,
+        min_attendance: request.min_attendance,
+        tournament_ids: request.tournament_ids,
+    };
+    season.validate()?;
+
+    let mut tx = pool.begin().await?;
+    let season_id = sqlx::query_scalar::<_, i32>(
+        "INSERT INTO seasons (name, scoring, league_points, drop_worst, min_attendance) VALUES ($1, $2, $3, $4, $5) RETURNING id"
+    )
+    .bind(&season.name)
+    .bind(season.scoring.as_str())
+    .bind(&season.league_points)
+    .bind(season.drop_worst)
+    .bind(season.min_attendance)
+    .fetch_one(&mut *tx)
+    .await?;
+    set_season_tournaments(&mut tx, season_id, &season.tournament_ids).await?;
+    let season = fetch_season(&mut tx, season_id).await?;
+    tx.commit().await?;
+    tracing::info!("season {} created by {}", season_id, user.username);
+
+    Ok((StatusCode::CREATED, Json(season)))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/v1/seasons/{season_id}",
+    params(
+        ("season_id" = i32, Path, description = "Season ID")
+    ),
+    request_body = UpdateSeasonRequest,
+    responses(
+        (status = 200, description = "Season updated", body = Season),
+        (status = 400, description = "Malformed request body or path parameter", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Season not found", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Missing or invalid token", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Scorekeeper role required", body = Problem, content_type = "application/problem+json"),
+        (status = 409, description = "Season name already taken", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "Season failed validation or references an unknown tournament", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "Unexpected server error", body = Problem, content_type = "application/problem+json")
+    ),
+    security(("bearer" = []))
+)]
+pub async fn update_season(
+    _: Scorekeeper,
+    State(pool): State<PgPool>,
+    Path(season_id): Path<i32>,
+    Json(request): Json<UpdateSeasonRequest>,
+) -> Result<Json<Season>, AppError> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("SELECT 1 FROM seasons WHERE id = $1 FOR UPDATE")
+        .bind(season_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::not_found("season"))?;
+
+    let mut season = fetch_season(&mut tx, season_id).await?;
+    let tournaments_changed = request.tournament_ids.is_some();
+    if let Some(name) = request.name {
+        season.name = name.trim().to_string();
+    }
+    season.scoring = request.scoring.unwrap_or(season.scoring);
+    season.league_points = request.league_points.unwrap_or(season.league_points);
+    season.drop_worst = request.drop_worst.unwrap_or(season.drop_worst);
+    season.min_attendance = request.min_attendance.unwrap_or(season.min_attendance);
+    season.tournament_ids = request.tournament_ids.unwrap_or(season.tournament_ids);
+    season.validate()?;
+
+    sqlx::query(
+        "UPDATE seasons SET name = $2, scoring = $3, league_points = $4, drop_worst = $5, min_attendance = $6 WHERE id = $1"
+    )
+    .bind(season_id)
+    .bind(&season.name)
+    .bind(season.scoring.as_str())
+    .bind(&season.league_points)
+    .bind(season.drop_worst)
+    .bind(season.min_attendance)
+    .execute(&mut *tx)
+    .await?;
+    if tournaments_changed {
+        set_season_tournaments(&mut tx, season_id, &season.tournament_ids).await?;
+    }
+    let season = fetch_season(&mut tx, season_id).await?;
+    tx.commit().await?;
+
+    Ok(Json(season))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/v1/seasons/{season_id}",
+    params(
+        ("season_id" = i32, Path, description = "Season ID")
+    ),
+    responses(
+        (status = 204, description = "Season deleted; its tournaments are kept"),
+        (status = 400, description = "Invalid path parameter", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Season not found", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Missing or invalid token", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Admin role required", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "Unexpected server error", body = Problem, content_type = "application/problem+json")
+    ),
+    security(("bearer" = []))
+)]
+pub async fn delete_season(
+    RequireRole(user): Admin,
+    State(pool): State<PgPool>,
+    Path(season_id): Path<i32>,
+) -> Result<StatusCode, AppError> {
+    let deleted = sqlx::query("DELETE FROM seasons WHERE id = $1")
+        .bind(season_id)
+        .execute(&pool)
+        .await?
+        .rows_affected();
+
+    if deleted == 0 {
+        return Err(AppError::not_found("season"));
+    }
+    tracing::info!("season {} deleted by {}", season_id, user.username);
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/seasons/{season_id}/standings",
+    params(
+        ("season_id" = i32, Path, description = "Season ID")
+    ),
+    responses(
+        (status = 200, description = "Season standings", body = SeasonStandings),
+        (status = 400, description = "Invalid path parameter", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Season not found", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "Unexpected server error", body = Problem, content_type = "application/problem+json")
+    )
+)]
+pub async fn get_season_standings(
+    State(pool): State<PgPool>,
+    Path(season_id): Path<i32>,
+) -> Result<Json<SeasonStandings>, AppError> {
+    let season = fetch_season(&mut *pool.acquire().await?, season_id).await?;
+
+    let tournaments = sqlx::query_as::<_, Tournament>(
+        "SELECT id, name, sub_name, date, location, ruleset_id FROM tournaments WHERE id = ANY($1) ORDER BY date, id"
+    )
+    .bind(&season.tournament_ids)
+    .fetch_all(&pool)
+    .await?;
+    let details = load_tournament_details(&pool, tournaments).await?;
+
+    let mut standings: HashMap<i32, SeasonStanding> = HashMap::new();
+    for detail in details {
+        for summary in detail.summary {
+            let standing = standings.entry(summary.player_id).or_insert_with(|| SeasonStanding {
+                place: None,
+                player_id: summary.player_id,
+                player_name: summary.player_name.clone(),
+                attendance: 0,
+                total_points: 0.0,
+                total_game_point: 0,
+                results: Vec::new(),
+            });
+            standing.attendance += 1;
+            standing.results.push(SeasonTournamentResult {
+                tournament_id: detail.info.id,
+                tournament_name: detail.info.name.clone(),
+                tournament_date: detail.info.date,
+                tournament_place: summary.tournament_place,
+                table_point: summary.total_point.table_point,
+                game_point: summary.total_point.game_point,
+                points: season.score(summary.tournament_place, summary.total_point.table_point),
+                dropped: false,
+            });
+        }
+    }
+
+    // Only the best (tournaments - drop_worst) results count, so missed
+    // tournaments are dropped before any result is
+    let counted = season.tournament_ids.len().saturating_sub(season.drop_worst as usize);
+    let mut standings: Vec<SeasonStanding> = standings.into_values().collect();
+    for standing in &mut standings {
+        count_best_results(standing, counted);
+    }
+    rank_standings(&mut standings, season.min_attendance);
+
+    Ok(Json(SeasonStandings { season, standings }))
+}
+
+/// Marks all but the `counted` best results of a player as dropped, best by
+/// points and then game points, and totals the rest.
+fn count_best_results(standing: &mut SeasonStanding, counted: usize) {
+    let mut by_points: Vec<usize> = (0..standing.results.len()).collect();
+    by_points.sort_by(|&a, &b| {
+        let (a, b) = (&standing.results[a], &standing.results[b]);
+        b.points
+            .partial_cmp(&a.points)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(b.game_point.cmp(&a.game_point))
+    });
+    for &index in by_points.iter().skip(counted) {
+        standing.results[index].dropped = true;
+    }
+    let kept = standing.results.iter().filter(|r| !r.dropped);
+    // Folded from 0.0, as an empty f32 sum is -0.0
+    standing.total_points = kept.clone().fold(0.0, |total, r| total + r.points);
+    standing.total_game_point = kept.map(|r| r.game_point).sum();
+}
+
+/// Orders players by points, then game points, with those below
+/// `min_attendance` after everyone ranked and left without a place. Players
+/// level on both share a place.
+fn rank_standings(standings: &mut [SeasonStanding], min_attendance: i32) {
+    let ranking_key = |s: &SeasonStanding| (s.attendance >= min_attendance, s.total_points, s.total_game_point);
+    standings.sort_by(|a, b| {
+        ranking_key(b)
+            .partial_cmp(&ranking_key(a))
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.player_id.cmp(&b.player_id))
+    });
+    for index in 0..standings.len() {
+        if standings[index].attendance < min_attendance {
+            break;
+        }
+        standings[index].place = match index {
+            _ if index > 0 && ranking_key(&standings[index - 1]) == ranking_key(&standings[index]) => {
+                standings[index - 1].place
+            }
+            _ => Some(index as i32 + 1),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A player with one result per `(points, game_point)`.
+    fn standing(player_id: i32, results: &[(f32, i32)]) -> SeasonStanding {
+        SeasonStanding {
+            place: None,
+            player_id,
+            player_name: format!("player {}", player_id),
+            attendance: results.len() as i32,
+            total_points: 0.0,
+            total_game_point: 0,
+            results: results
+                .iter()
+                .enumerate()
+                .map(|(index, &(points, game_point))| SeasonTournamentResult {
+                    tournament_id: index as i32 + 1,
+                    tournament_name: format!("tournament {}", index + 1),
+                    tournament_date: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap() + chrono::Days::new(index as u64),
+                    tournament_place: 1,
+                    table_point: points,
+                    game_point,
+                    points,
+                    dropped: false,
+                })
+                .collect(),
+        }
+    }
+
+    fn dropped(standing: &SeasonStanding) -> Vec<bool> {
+        standing.results.iter().map(|r| r.dropped).collect()
+    }
+
+    fn ranked(mut standings: Vec<SeasonStanding>, min_attendance: i32) -> Vec<(i32, Option<i32>)> {
+        for standing in &mut standings {
+            let counted = standing.results.len();
+            count_best_results(standing, counted);
+        }
+        rank_standings(&mut standings, min_attendance);
+        standings.iter().map(|s| (s.player_id, s.place)).collect()
+    }
+
+    #[test]
+    fn drops_the_lowest_scoring_results() {
+        let mut player = standing(1, &[(12.0, 100), (8.0, -50), (11.0, 20), (9.0, 0)]);
+        count_best_results(&mut player, 2);
+        assert_eq!(dropped(&player), [false, true, false, true]);
+        assert_eq!(player.total_points, 23.0);
+        assert_eq!(player.total_game_point, 120);
+    }
+
+    #[test]
+    fn game_points_decide_which_equal_result_is_dropped() {
+        let mut player = standing(1, &[(10.0, -30), (10.0, 40), (12.0, 0)]);
+        count_best_results(&mut player, 2);
+        assert_eq!(dropped(&player), [true, false, false]);
+        assert_eq!(player.total_game_point, 40);
+    }
+
+    #[test]
+    fn missed_tournaments_are_dropped_first() {
+        // Attended 3 of 4 with one to drop: the missed one is the drop
+        let mut player = standing(1, &[(12.0, 0), (8.0, 0), (11.0, 0)]);
+        count_best_results(&mut player, 3);
+        assert_eq!(dropped(&player), [false, false, false]);
+        assert_eq!(player.total_points, 31.0);
+    }
+
+    #[test]
+    fn ranks_by_points_then_game_points() {
+        let standings = vec![
+            standing(1, &[(10.0, 0)]),
+            standing(2, &[(12.0, 0)]),
+            standing(3, &[(10.0, 50)]),
+        ];
+        assert_eq!(ranked(standings, 0), [(2, Some(1)), (3, Some(2)), (1, Some(3))]);
+    }
+
+    #[test]
+    fn players_level_on_points_and_game_points_share_a_place() {
+        let standings = vec![
+            standing(3, &[(10.0, 0)]),
+            standing(1, &[(10.0, 0)]),
+            standing(2, &[(5.0, 0)]),
+        ];
+        assert_eq!(ranked(standings, 0), [(1, Some(1)), (3, Some(1)), (2, Some(3))]);
+    }
+
+    #[test]
+    fn players_below_minimum_attendance_are_listed_unranked() {
+        let standings = vec![
+            standing(1, &[(30.0, 0)]),
+            standing(2, &[(10.0, 0), (5.0, 0)]),
+            standing(3, &[(8.0, 0), (4.0, 0)]),
+        ];
+        assert_eq!(ranked(standings, 2), [(2, Some(1)), (3, Some(2)), (1, None)]);
+    }
+}
@@ -0,0 +1,461 @@
+use axum::{extract::State, http::StatusCode};
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgPool;
+use std::collections::HashSet;
+use utoipa::ToSchema;
+
+use crate::auth::{Admin, RequireRole, Scorekeeper};
+use crate::error::{AppError, Problem};
+use crate::extract::{Json, Path};
+use crate::rating;
+use crate::validation::ValidationErrors;
+
+/// Columns selected for every `Session` query, with `s` aliasing `sessions`.
+const SESSION_COLUMNS: &str =
+    "s.id, s.tournament_id, s.round_number, s.name, (SELECT COUNT(*) FROM games g WHERE g.session_id = s.id) AS game_count";
+
+/// A round of a tournament.
+#[derive(Serialize, Deserialize, sqlx::FromRow, Debug, ToSchema)]
+pub struct Session {
+    id: i32,
+    tournament_id: i32,
+    /// Position of the round within its tournament, from 1
+    round_number: i32,
+    name: String,
+    game_count: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct CreateSessionRequest {
+    /// Defaults to "N回戦"
+    name: Option<String>,
+    /// Defaults to the round after the last one
+    round_number: Option<i32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct UpdateSessionRequest {
+    name: Option<String>,
+    /// Must not be taken by another round of the tournament
+    round_number: Option<i32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct ReorderSessionsRequest {
+    /// Every session of the tournament, in their new order; they are
+    /// renumbered from 1
+    session_ids: Vec<i32>,
+}
+
+fn validate_session(name: Option<&str>, round_number: Option<i32>) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::default();
+    if name.is_some_and(|name| name.trim().is_empty()) {
+        errors.push("name", "empty_name", "session name must not be empty");
+    }
+    if round_number.is_some_and(|round_number| round_number <= 0) {
+        errors.push("round_number", "not_positive", "round_number must be greater than 0");
+    }
+    errors.into_result(())
+}
+
+/// Checks that `session_ids` lists each of the tournament's sessions, given
+/// as `current`, exactly once.
+fn validate_session_order(current: &HashSet<i32>, session_ids: &[i32]) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::default();
+    let requested: HashSet<i32> = session_ids.iter().copied().collect();
+    if requested != *current || requested.len() != session_ids.len() {
+        errors.push(
+            "session_ids",
+            "session_set_mismatch",
+            "session_ids must list every session of the tournament exactly once",
+        );
+    }
+    errors.into_result(())
+}
+
+/// Numbers the sessions from 1 in the order given.
+async fn renumber_sessions(conn: &mut sqlx::PgConnection, session_ids: &[i32]) -> Result<(), AppError> {
+    // Numbers are swapped around within one statement, so check them at commit
+    sqlx::query("SET CONSTRAINTS sessions_tournament_round_key DEFERRED")
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query(
+        r#"
+        UPDATE sessions s SET round_number = ordered.round_number
+        FROM UNNEST($1::INTEGER[]) WITH ORDINALITY AS ordered (id, round_number)
+        WHERE s.id = ordered.id
+        "#
+    )
+    .bind(session_ids)
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+/// Creates a round, numbered after the last one unless `round_number` is
+/// given and named "N回戦" unless `name` is given.
+pub async fn insert_session(
+    conn: &mut sqlx::PgConnection,
+    tournament_id: i32,
+    round_number: Option<i32>,
+    name: Option<&str>,
+) -> Result<i32, AppError> {
+    let round_number = match round_number {
+        Some(round_number) => round_number,
+        None => {
+            sqlx::query_scalar::<_, i32>(
+                "SELECT COALESCE(MAX(round_number), 0) + 1 FROM sessions WHERE tournament_id = $1"
+            )
+            .bind(tournament_id)
+            .fetch_one(&mut *conn)
+            .await?
+        }
+    };
+    let name = match name {
+        Some(name) => name.trim().to_string(),
+        None => format!("{}回戦", round_number),
+    };
+
+    let id = sqlx::query_scalar::<_, i32>(
+        "INSERT INTO sessions (tournament_id, round_number, name) VALUES ($1, $2, $3) RETURNING id"
+    )
+    .bind(tournament_id)
+    .bind(round_number)
+    .bind(&name)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    Ok(id)
+}
+
+async fn fetch_session(conn: &mut sqlx::PgConnection, session_id: i32) -> Result<Session, AppError> {
+    let query = format!("SELECT {} FROM sessions s WHERE s.id = $1", SESSION_COLUMNS);
+    sqlx::query_as::<_, Session>(&query)
+        .bind(session_id)
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or_else(|| AppError::not_found("session"))
+}
+
+async fn tournament_exists(conn: &mut sqlx::PgConnection, tournament_id: i32) -> Result<(), AppError> {
+    let exists = sqlx::query_scalar::<_, bool>("SELECT EXISTS (SELECT 1 FROM tournaments WHERE id = $1)")
+        .bind(tournament_id)
+        .fetch_one(&mut *conn)
+        .await?;
+    if !exists {
+        return Err(AppError::not_found("tournament"));
+    }
+    Ok(())
+}
+
+async fn list_sessions(conn: &mut sqlx::PgConnection, tournament_id: i32) -> Result<Vec<Session>, AppError> {
+    let query = format!(
+        "SELECT {} FROM sessions s WHERE s.tournament_id = $1 ORDER BY s.round_number",
+        SESSION_COLUMNS
+    );
+    let sessions = sqlx::query_as::<_, Session>(&query)
+        .bind(tournament_id)
+        .fetch_all(&mut *conn)
+        .await?;
+    Ok(sessions)
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/tournaments/{tournament_id}/sessions",
+    params(
+        ("tournament_id" = i32, Path, description = "Tournament ID")
+    ),
+    responses(
+        (status = 200, description = "Rounds of the tournament in order", body = [Session]),
+        (status = 400, description = "Invalid path parameter", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Tournament not found", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "Unexpected server error", body = Problem, content_type = "application/problem+json")
+    )
+)]
+pub async fn get_sessions(
+    State(pool): State<PgPool>,
+    Path(tournament_id): Path<i32>,
+) -> Result<Json<Vec<Session>>, AppError> {
+    let mut conn = pool.acquire().await?;
+    tournament_exists(&mut conn, tournament_id).await?;
+    let sessions = list_sessions(&mut conn, tournament_id).await?;
+    Ok(Json(sessions))
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/tournaments/{tournament_id}/sessions",
+    params(
+        ("tournament_id" = i32, Path, description = "Tournament ID")
+    ),
+    request_body = CreateSessionRequest,
+    responses(
+        (status = 201, description = "Session created", body = Session),
+        (status = 400, description = "Malformed request body or path parameter", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Tournament not found", body = Problem, content_type = "application/problem+json"),
+        (status = 409, description = "Round number or name already taken in the tournament", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "Session failed validation", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Missing or invalid token", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Scorekeeper role required", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "Unexpected server error", body = Problem, content_type = "application/problem+json")
+    ),
+    security(("bearer" = []))
+)]
+pub async fn create_session(
+    _: Scorekeeper,
+    State(pool): State<PgPool>,
+    Path(tournament_id): Path<i32>,
+    Json(request): Json<CreateSessionRequest>,
+) -> Result<(StatusCode, Json<Session>), AppError> {
+    validate_session(request.name.as_deref(), request.round_number)?;
+
+    let mut tx = pool.begin().await?;
+    tournament_exists(&mut tx, tournament_id).await?;
+    let session_id = insert_session(&mut tx, tournament_id, request.round_number, request.name.as_deref()).await?;
+    let session = fetch_session(&mut tx, session_id).await?;
+    tx.commit().await?;
+
+    Ok((StatusCode::CREATED, Json(session)))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/v1/sessions/{session_id}",
+    params(
+        ("session_id" = i32, Path, description = "Session ID")
+    ),
+    request_body = UpdateSessionRequest,
+    responses(
+        (status = 200, description = "Session updated", body = Session),
+        (status = 400, description = "Malformed request body or path parameter", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Session not found", body = Problem, content_type = "application/problem+json"),
+        (status = 409, description = "Round number or name already taken in the tournament", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "Session failed validation", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Missing or invalid token", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Scorekeeper role required", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "Unexpected server error", body = Problem, content_type = "application/problem+json")
+    ),
+    security(("bearer" = []))
+)]
+pub async fn update_session(
+    _: Scorekeeper,
+    State(pool): State<PgPool>,
+    Path(session_id): Path<i32>,
+    Json(request): Json<UpdateSessionRequest>,
+) -> Result<Json<Session>, AppError> {
+    validate_session(request.name.as_deref(), request.round_number)?;
+
+    let mut tx = pool.begin().await?;
+    let mut previous_order = None;
+    if request.round_number.is_some() {
+        rating::lock_ratings(&mut tx).await?;
+        previous_order = rating::session_order(&mut tx, session_id).await?;
+    }
+    let updated = sqlx::query(
+        "UPDATE sessions SET name = COALESCE($2, name), round_number = COALESCE($3, round_number) WHERE id = $1"
+    )
+    .bind(session_id)
+    .bind(request.name.as_deref().map(str::trim))
+    .bind(request.round_number)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+    if updated == 0 {
+        return Err(AppError::not_found("session"));
+    }
+    // Ratings replay games round by round
+    if request.round_number.is_some() {
+        let order = rating::session_order(&mut tx, session_id).await?;
+        rating::replay_ratings_from(&mut tx, rating::earliest(previous_order, order)).await?;
+    }
+    let session = fetch_session(&mut tx, session_id).await?;
+    tx.commit().await?;
+
+    Ok(Json(session))
+}
+
+#[utoipa::path(
+    put,
+    path = "/v1/tournaments/{tournament_id}/sessions/order",
+    params(
+        ("tournament_id" = i32, Path, description = "Tournament ID")
+    ),
+    request_body = ReorderSessionsRequest,
+    responses(
+        (status = 200, description = "Rounds of the tournament in their new order", body = [Session]),
+        (status = 400, description = "Malformed request body or path parameter", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Tournament not found", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "session_ids is not exactly the tournament's sessions", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Missing or invalid token", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Scorekeeper role required", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "Unexpected server error", body = Problem, content_type = "application/problem+json")
+    ),
+    security(("bearer" = []))
+)]
+pub async fn reorder_sessions(
+    _: Scorekeeper,
+    State(pool): State<PgPool>,
+    Path(tournament_id): Path<i32>,
+    Json(request): Json<ReorderSessionsRequest>,
+) -> Result<Json<Vec<Session>>, AppError> {
+    let mut tx = pool.begin().await?;
+    rating::lock_ratings(&mut tx).await?;
+    tournament_exists(&mut tx, tournament_id).await?;
+    let previous_order = rating::tournament_order(&mut tx, tournament_id).await?;
+
+    let current: HashSet<i32> = sqlx::query_scalar::<_, i32>("SELECT id FROM sessions WHERE tournament_id = $1 FOR UPDATE")
+        .bind(tournament_id)
+        .fetch_all(&mut *tx)
+        .await?
+        .into_iter()
+        .collect();
+    validate_session_order(&current, &request.session_ids)?;
+    renumber_sessions(&mut tx, &request.session_ids).await?;
+    let order = rating::tournament_order(&mut tx, tournament_id).await?;
+    rating::replay_ratings_from(&mut tx, rating::earliest(previous_order, order)).await?;
+
+    let sessions = list_sessions(&mut tx, tournament_id).await?;
+    tx.commit().await?;
+
+    Ok(Json(sessions))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/v1/sessions/{session_id}",
+    params(
+        ("session_id" = i32, Path, description = "Session ID")
+    ),
+    responses(
+        (status = 204, description = "Session deleted"),
+        (status = 400, description = "Invalid path parameter", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Session not found", body = Problem, content_type = "application/problem+json"),
+        (status = 409, description = "Session still has games", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Missing or invalid token", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Admin role required", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "Unexpected server error", body = Problem, content_type = "application/problem+json")
+    ),
+    security(("bearer" = []))
+)]
+pub async fn delete_session(
+    RequireRole(user): Admin,
+    State(pool): State<PgPool>,
+    Path(session_id): Path<i32>,
+) -> Result<StatusCode, AppError> {
+    let mut tx = pool.begin().await?;
+    let session = fetch_session(&mut tx, session_id).await?;
+    if session.game_count > 0 {
+        return Err(AppError::conflict(
+            "session_has_games",
+            format!("session {} has games; move or delete them first", session_id),
+        ));
+    }
+
+    sqlx::query("DELETE FROM sessions WHERE id = $1")
+        .bind(session_id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+    tracing::info!("session {} deleted by {}", session_id, user.username);
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(ids: &[i32]) -> HashSet<i32> {
+        ids.iter().copied().collect()
+    }
+
+    fn codes(result: Result<(), ValidationErrors>) -> Vec<String> {
+        result.err().map_or_else(Vec::new, |errors| errors.violations.into_iter().map(|v| v.code).collect())
+    }
+
+    #[test]
+    fn any_order_of_the_tournament_sessions_is_accepted() {
+        assert!(validate_session_order(&ids(&[4, 5, 6]), &[6, 4, 5]).is_ok());
+        assert!(validate_session_order(&ids(&[]), &[]).is_ok());
+    }
+
+    #[test]
+    fn duplicate_session_is_rejected() {
+        assert_eq!(codes(validate_session_order(&ids(&[4, 5]), &[4, 5, 5])), ["session_set_mismatch"]);
+        // Same set, but one session listed twice in place of another
+        assert_eq!(codes(validate_session_order(&ids(&[4, 5, 6]), &[4, 4, 5, 6])), ["session_set_mismatch"]);
+    }
+
+    #[test]
+    fn missing_session_is_rejected() {
+        assert_eq!(codes(validate_session_order(&ids(&[4, 5, 6]), &[6, 4])), ["session_set_mismatch"]);
+    }
+
+    #[test]
+    fn session_of_another_tournament_is_rejected() {
+        assert_eq!(codes(validate_session_order(&ids(&[4, 5]), &[4, 5, 9])), ["session_set_mismatch"]);
+        assert_eq!(codes(validate_session_order(&ids(&[4, 5]), &[4, 9])), ["session_set_mismatch"]);
+    }
+
+    async fn create_tournament(conn: &mut sqlx::PgConnection) -> i32 {
+        sqlx::query_scalar(
+            r#"
+            INSERT INTO tournaments (name, sub_name, date, location, ruleset_id)
+            SELECT 'test', '', '2025-01-01', '', id FROM rulesets WHERE name = 'standard'
+            RETURNING id
+            "#
+        )
+        .fetch_one(&mut *conn)
+        .await
+        .unwrap()
+    }
+
+    async fn rounds(conn: &mut sqlx::PgConnection, tournament_id: i32) -> Vec<(i32, String)> {
+        list_sessions(conn, tournament_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|s| (s.round_number, s.name))
+            .collect()
+    }
+
+    #[sqlx::test]
+    #[ignore = "needs DATABASE_URL pointing at a Postgres server"]
+    async fn sessions_are_numbered_after_the_last_round(pool: PgPool) {
+        let mut conn = pool.acquire().await.unwrap();
+        let tournament_id = create_tournament(&mut conn).await;
+        let other_id = create_tournament(&mut conn).await;
+        insert_session(&mut conn, other_id, Some(7), None).await.unwrap();
+
+        insert_session(&mut conn, tournament_id, None, None).await.unwrap();
+        insert_session(&mut conn, tournament_id, Some(5), Some(" 決勝 ")).await.unwrap();
+        insert_session(&mut conn, tournament_id, None, None).await.unwrap();
+
+        assert_eq!(
+            rounds(&mut conn, tournament_id).await,
+            [(1, "1回戦".to_string()), (5, "決勝".to_string()), (6, "6回戦".to_string())]
+        );
+        let taken = insert_session(&mut conn, tournament_id, Some(5), None).await.unwrap_err();
+        assert_eq!(taken.code(), "duplicate");
+    }
+
+    #[sqlx::test]
+    #[ignore = "needs DATABASE_URL pointing at a Postgres server"]
+    async fn renumbering_swaps_rounds_within_a_transaction(pool: PgPool) {
+        let mut tx = pool.begin().await.unwrap();
+        let tournament_id = create_tournament(&mut tx).await;
+        let first = insert_session(&mut tx, tournament_id, None, Some("A")).await.unwrap();
+        let second = insert_session(&mut tx, tournament_id, None, Some("B")).await.unwrap();
+        let third = insert_session(&mut tx, tournament_id, None, Some("C")).await.unwrap();
+
+        renumber_sessions(&mut tx, &[third, first, second]).await.unwrap();
+        tx.commit().await.unwrap();
+
+        let mut conn = pool.acquire().await.unwrap();
+        assert_eq!(
+            rounds(&mut conn, tournament_id).await,
+            [(1, "C".to_string()), (2, "A".to_string()), (3, "B".to_string())]
+        );
+    }
+}
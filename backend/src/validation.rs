@@ -0,0 +1,257 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use utoipa::ToSchema;
+
+use crate::hand_log;
+use crate::ruleset::Ruleset;
+use crate::scoring::{self, DerivedPoints};
+use crate::{seat_order, CreateGameRequest};
+
+pub const PLAYERS_PER_GAME: usize = 4;
+const TABLE_POINT_TOLERANCE: f32 = 0.001;
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct Violation {
+    pub field: String,
+    pub code: String,
+    pub message: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct ValidationErrors {
+    pub violations: Vec<Violation>,
+}
+
+impl ValidationErrors {
+    pub fn push(&mut self, field: impl Into<String>, code: &str, message: impl Into<String>) {
+        self.violations.push(Violation {
+            field: field.into(),
+            code: code.to_string(),
+            message: message.into(),
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    pub fn into_result<T>(self, value: T) -> Result<T, ValidationErrors> {
+        if self.is_empty() {
+            Ok(value)
+        } else {
+            Err(self)
+        }
+    }
+}
+
+/// Checks a game submission before anything is written, collecting every
+/// violation instead of stopping at the first one. On success returns the
+/// points derived from the game points under `ruleset`, in request order.
+/// Points are derived in seating order, which decides ties under
+/// `TieHandling::SeatOrder`.
+pub fn validate_game(request: &CreateGameRequest, ruleset: &Ruleset) -> Result<Vec<DerivedPoints>, ValidationErrors> {
+    let mut errors = ValidationErrors::default();
+    let results = &request.player_results;
+
+    if results.len() != PLAYERS_PER_GAME {
+        errors.push(
+            "player_results",
+            "player_count",
+            format!("a game needs exactly {} players, got {}", PLAYERS_PER_GAME, results.len()),
+        );
+    }
+
+    if request.round_number.is_none() && request.session_name.is_none() {
+        errors.push("session_name", "missing_session", "either round_number or session_name is required");
+    }
+    if request.session_name.as_deref().is_some_and(|name| name.trim().is_empty()) {
+        errors.push("session_name", "empty_session_name", "session name must not be empty");
+    }
+    if request.round_number.is_some_and(|round_number| round_number <= 0) {
+        errors.push("round_number", "not_positive", "round_number must be greater than 0");
+    }
+    if request.table_no.is_some_and(|table_no| table_no <= 0) {
+        errors.push("table_no", "not_positive", "table_no must be greater than 0");
+    }
+
+    let seated = results.iter().filter(|r| r.seat_wind.is_some()).count();
+    if seated != 0 && seated != results.len() {
+        errors.push(
+            "player_results",
+            "partial_seat_winds",
+            "seat_wind must be given for every player or for none",
+        );
+    }
+    let mut taken = HashSet::new();
+    for (index, result) in results.iter().enumerate() {
+        if let Some(wind) = result.seat_wind {
+            if !taken.insert(wind) {
+                errors.push(
+                    format!("player_results[{}].seat_wind", index),
+                    "duplicate_seat_wind",
+                    format!("more than one player is seated {}", wind.as_str()),
+                );
+            }
+        }
+    }
+
+    let mut seen = HashSet::new();
+    for (index, result) in results.iter().enumerate() {
+        if !seen.insert(result.player_id) {
+            errors.push(
+                format!("player_results[{}].player_id", index),
+                "duplicate_player",
+                format!("player {} appears more than once", result.player_id),
+            );
+        }
+    }
+
+    let order = seat_order(results);
+    let game_points: Vec<i32> = order.iter().map(|&index| results[index].game_point).collect();
+    let mut seated_points: Vec<(usize, DerivedPoints)> = order
+        .into_iter()
+        .zip(scoring::derive_points(ruleset, &game_points))
+        .collect();
+    seated_points.sort_by_key(|&(index, _)| index);
+    let points: Vec<DerivedPoints> = seated_points.into_iter().map(|(_, derived)| derived).collect();
+    for (index, (result, derived)) in results.iter().zip(&points).enumerate() {
+        if let Some(sent) = result.table_point {
+            if (sent - derived.table_point).abs() > TABLE_POINT_TOLERANCE {
+                errors.push(
+                    format!("player_results[{}].table_point", index),
+                    "table_point_mismatch",
+                    format!("table point {} does not match {} derived from game points", sent, derived.table_point),
+                );
+            }
+        }
+    }
+
+    let game_point_sum: i64 = results.iter().map(|r| r.game_point as i64).sum();
+    let forfeit = request.forfeit_game_point.unwrap_or(0) as i64;
+    if game_point_sum + forfeit != 0 {
+        errors.push(
+            "player_results",
+            "game_point_sum",
+            format!(
+                "game points ({}) plus forfeit_game_point ({}) sum to {}, expected 0",
+                game_point_sum,
+                forfeit,
+                game_point_sum + forfeit
+            ),
+        );
+    }
+
+    hand_log::validate_hands(request, ruleset, &mut errors);
+
+    errors.into_result(points)
+}
+
+/// Trims a player name and checks it is usable, returning the trimmed name.
+pub fn validate_player_name(name: &str) -> Result<String, ValidationErrors> {
+    let mut errors = ValidationErrors::default();
+    let name = name.trim();
+
+    if name.is_empty() {
+        errors.push("name", "empty_name", "player name must not be empty");
+    }
+
+    errors.into_result(name.to_string())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// A game in round 1 of tournament 1 with players 1-4 seated East to
+    /// North in order.
+    pub(crate) fn game(game_points: [i32; 4]) -> serde_json::Value {
+        json!({
+            "tournament_id": 1,
+            "round_number": 1,
+            "player_results": (0..4)
+                .map(|index| json!({ "player_id": index + 1, "game_point": game_points[index] }))
+                .collect::<Vec<_>>(),
+        })
+    }
+
+    pub(crate) fn request(value: serde_json::Value) -> CreateGameRequest {
+        serde_json::from_value(value).unwrap()
+    }
+
+    pub(crate) fn codes(result: Result<Vec<DerivedPoints>, ValidationErrors>) -> Vec<String> {
+        result.err().map_or_else(Vec::new, |errors| errors.violations.into_iter().map(|v| v.code).collect())
+    }
+
+    #[test]
+    fn valid_game_returns_points_in_request_order() {
+        let points = validate_game(&request(game([-7, 57, 109, -159])), &Ruleset::standard()).unwrap();
+        let table_points: Vec<f32> = points.iter().map(|p| p.table_point).collect();
+        let place_points: Vec<f32> = points.iter().map(|p| p.place_point).collect();
+        assert_eq!(table_points, [2.0, 3.0, 4.0, 1.0]);
+        assert_eq!(place_points, [-1.0, 1.0, 3.0, -3.0]);
+    }
+
+    #[test]
+    fn points_follow_seat_winds_not_request_order() {
+        let mut value = game([-7, 57, 109, -159]);
+        for (result, wind) in value["player_results"].as_array_mut().unwrap().iter_mut().zip(["north", "west", "south", "east"]) {
+            result["seat_wind"] = json!(wind);
+        }
+        let points = validate_game(&request(value), &Ruleset::standard()).unwrap();
+        let table_points: Vec<f32> = points.iter().map(|p| p.table_point).collect();
+        assert_eq!(table_points, [2.0, 3.0, 4.0, 1.0]);
+    }
+
+    #[test]
+    fn forfeit_balances_the_game_point_sum() {
+        let mut value = game([51, -21, -76, -14]);
+        assert_eq!(codes(validate_game(&request(value.clone()), &Ruleset::standard())), ["game_point_sum"]);
+        value["forfeit_game_point"] = json!(60);
+        assert!(validate_game(&request(value), &Ruleset::standard()).is_ok());
+    }
+
+    #[test]
+    fn sent_table_points_must_match_derived_ones() {
+        let mut value = game([-7, 57, 109, -159]);
+        for (result, table_point) in value["player_results"].as_array_mut().unwrap().iter_mut().zip([2.0, 3.0, 1.0, 4.0]) {
+            result["table_point"] = json!(table_point);
+        }
+        assert_eq!(
+            codes(validate_game(&request(value), &Ruleset::standard())),
+            ["table_point_mismatch", "table_point_mismatch"]
+        );
+    }
+
+    #[test]
+    fn collects_every_violation() {
+        let mut value = game([10, 10, 10, 10]);
+        value["round_number"] = json!(null);
+        value["table_no"] = json!(0);
+        value["player_results"][1]["player_id"] = json!(1);
+        value["player_results"][0]["seat_wind"] = json!("east");
+        assert_eq!(
+            codes(validate_game(&request(value), &Ruleset::standard())),
+            ["missing_session", "not_positive", "partial_seat_winds", "duplicate_player", "game_point_sum"]
+        );
+    }
+
+    #[test]
+    fn rejects_wrong_player_count_and_duplicate_seats() {
+        let mut value = game([10, -10, 0, 0]);
+        value["player_results"].as_array_mut().unwrap().pop();
+        for result in value["player_results"].as_array_mut().unwrap() {
+            result["seat_wind"] = json!("south");
+        }
+        assert_eq!(
+            codes(validate_game(&request(value), &Ruleset::standard())),
+            ["player_count", "duplicate_seat_wind", "duplicate_seat_wind"]
+        );
+    }
+
+    #[test]
+    fn player_names_are_trimmed_and_required() {
+        assert_eq!(validate_player_name("  Alice ").unwrap(), "Alice");
+        assert!(validate_player_name("   ").is_err());
+    }
+}
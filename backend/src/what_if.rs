@@ -0,0 +1,646 @@
+use axum::extract::State;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgPool;
+use std::collections::HashMap;
+use utoipa::ToSchema;
+
+use crate::error::{AppError, Problem};
+use crate::extract::Json;
+use crate::ruleset::RulesetDefinition;
+use crate::scoring::{self, DerivedPoints};
+use crate::validation::ValidationErrors;
+use crate::{HandStats, PlayerStats, RoundPoint, SeatWind, TotalPoint, TournamentSummary};
+
+/// Which games a what-if recalculation covers.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WhatIfScope {
+    Tournament { tournament_id: i32 },
+    /// Every tournament with this sub name
+    Series { sub_name: String },
+    AllTime,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct WhatIfRequest {
+    ruleset: RulesetDefinition,
+    scope: WhatIfScope,
+}
+
+/// A player's standing over the whole scope, ranked by total place points
+/// and then total game points.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct StandingChange {
+    player_id: i32,
+    player_name: String,
+    current_rank: i32,
+    new_rank: i32,
+    /// Positive when the player moves up under the new ruleset
+    rank_change: i32,
+    current: PlayerStats,
+    new: PlayerStats,
+}
+
+/// A player's place in one tournament, ranked by total place points and
+/// then total game points under each ruleset. The tournament page itself
+/// ranks by table points, which uma and oka do not change.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct SummaryChange {
+    player_id: i32,
+    player_name: String,
+    current_place: i32,
+    new_place: i32,
+    /// Positive when the player moves up under the new ruleset
+    place_change: i32,
+    current_place_point: f32,
+    new_place_point: f32,
+    current: TournamentSummary,
+    new: TournamentSummary,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct TournamentRankingChange {
+    tournament_id: i32,
+    tournament_name: String,
+    #[schema(value_type = String, format = Date)]
+    tournament_date: NaiveDate,
+    rankings: Vec<SummaryChange>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct WhatIfResponse {
+    /// Ordered by new rank
+    standings: Vec<StandingChange>,
+    /// Tournaments in the scope by date, each ordered by new place
+    tournaments: Vec<TournamentRankingChange>,
+}
+
+#[derive(sqlx::FromRow)]
+struct ResultRow {
+    tournament_id: i32,
+    tournament_name: String,
+    tournament_date: NaiveDate,
+    session_id: i32,
+    round_number: i32,
+    session_name: String,
+    game_id: i32,
+    player_id: i32,
+    player_name: String,
+    seat_wind: String,
+    game_point: i32,
+    table_point: f32,
+    place_point: f32,
+}
+
+/// One player's result in one game under a single ruleset, with the
+/// placements it spans.
+struct ScoredResult {
+    tournament_id: i32,
+    session_id: i32,
+    player_id: i32,
+    seat_wind: Option<SeatWind>,
+    game_point: i32,
+    points: DerivedPoints,
+    /// First placement spanned, 0-based
+    place_from: usize,
+    tied_players: usize,
+}
+
+/// Places every result of a game by table points, the way stored points are
+/// read by `player_stats_filtered`: equal table points share placements.
+fn score_game(rows: &[ResultRow], points: &[DerivedPoints]) -> Vec<ScoredResult> {
+    rows.iter()
+        .zip(points)
+        .map(|(row, &own)| ScoredResult {
+            tournament_id: row.tournament_id,
+            session_id: row.session_id,
+            player_id: row.player_id,
+            seat_wind: SeatWind::from_db(&row.seat_wind),
+            game_point: row.game_point,
+            points: own,
+            place_from: points.iter().filter(|p| p.table_point > own.table_point).count(),
+            tied_players: points.iter().filter(|p| p.table_point == own.table_point).count(),
+        })
+        .collect()
+}
+
+/// Share of `placement` (0-based) a result is credited with.
+fn place_credit(result: &ScoredResult, placement: usize) -> f32 {
+    if (result.place_from..result.place_from + result.tied_players).contains(&placement) {
+        1.0 / result.tied_players as f32
+    } else {
+        0.0
+    }
+}
+
+fn round2(value: f32) -> f32 {
+    (value * 100.0).round() / 100.0
+}
+
+/// Game count, average place and average game points of the results
+/// played from `wind`; places are read from table points.
+fn seat_stats(results: &[&ScoredResult], wind: SeatWind) -> (Option<i64>, Option<f32>, Option<f32>) {
+    let seated: Vec<&&ScoredResult> = results.iter().filter(|r| r.seat_wind == Some(wind)).collect();
+    if seated.is_empty() {
+        return (Some(0), None, None);
+    }
+    let games = seated.len() as f32;
+    let total_place: f32 = seated.iter().map(|r| 5.0 - r.points.table_point).sum();
+    let total_gp: i64 = seated.iter().map(|r| r.game_point as i64).sum();
+    (Some(seated.len() as i64), Some(total_place / games), Some(total_gp as f32 / games))
+}
+
+/// Aggregates `PlayerStats` over the results, mirroring `player_stats_filtered`.
+/// Hand figures do not depend on the ruleset and are taken as they are.
+fn player_stats(
+    results: &[ScoredResult],
+    names: &HashMap<i32, String>,
+    hand_stats: &HashMap<i32, HandStats>,
+) -> HashMap<i32, PlayerStats> {
+    let mut by_player: HashMap<i32, Vec<&ScoredResult>> = HashMap::new();
+    for result in results {
+        by_player.entry(result.player_id).or_default().push(result);
+    }
+
+    by_player
+        .into_iter()
+        .map(|(player_id, results)| {
+            let games = results.len() as f32;
+            let total_gp: i64 = results.iter().map(|r| r.game_point as i64).sum();
+            let total_tp: f32 = results.iter().map(|r| r.points.table_point).sum();
+            let total_pp: f32 = results.iter().map(|r| r.points.place_point).sum();
+            let counts: Vec<f32> = (0..4)
+                .map(|placement| results.iter().map(|r| place_credit(r, placement)).sum())
+                .collect();
+            let (east_game_count, east_avg_place, east_avg_gp) = seat_stats(&results, SeatWind::East);
+            let (south_game_count, south_avg_place, south_avg_gp) = seat_stats(&results, SeatWind::South);
+            let (west_game_count, west_avg_place, west_avg_gp) = seat_stats(&results, SeatWind::West);
+            let (north_game_count, north_avg_place, north_avg_gp) = seat_stats(&results, SeatWind::North);
+            let stats = PlayerStats {
+                player_id,
+                player_name: names.get(&player_id).cloned().unwrap_or_default(),
+                game_count: Some(results.len() as i64),
+                total_gp: Some(total_gp),
+                total_tp: Some(total_tp),
+                total_pp: Some(total_pp),
+                first_place_count: Some(counts[0]),
+                second_place_count: Some(counts[1]),
+                third_place_count: Some(counts[2]),
+                fourth_place_count: Some(counts[3]),
+                shared_place_count: Some(results.iter().filter(|r| r.tied_players > 1).count() as i64),
+                avg_gp: Some(total_gp as f32 / games),
+                avg_tp: Some(total_tp / games),
+                avg_pp: Some(total_pp / games),
+                first_place_ratio: Some(round2(100.0 * counts[0] / games)),
+                second_place_ratio: Some(round2(100.0 * counts[1] / games)),
+                third_place_ratio: Some(round2(100.0 * counts[2] / games)),
+                fourth_place_ratio: Some(round2(100.0 * counts[3] / games)),
+                east_game_count,
+                east_avg_place,
+                east_avg_gp,
+                south_game_count,
+                south_avg_place,
+                south_avg_gp,
+                west_game_count,
+                west_avg_place,
+                west_avg_gp,
+                north_game_count,
+                north_avg_place,
+                north_avg_gp,
+                hands: hand_stats.get(&player_id).cloned().unwrap_or_default(),
+            };
+            (player_id, stats)
+        })
+        .collect()
+}
+
+/// Ranks players by total place points, then total game points. Equal
+/// players share a rank.
+fn standing_ranks(stats: &HashMap<i32, PlayerStats>) -> HashMap<i32, i32> {
+    let key = |s: &PlayerStats| (s.total_pp.unwrap_or(0.0), s.total_gp.unwrap_or(0));
+    let mut ordered: Vec<&PlayerStats> = stats.values().collect();
+    ordered.sort_by(|a, b| {
+        key(b)
+            .partial_cmp(&key(a))
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.player_id.cmp(&b.player_id))
+    });
+
+    let mut ranks = HashMap::new();
+    for (index, s) in ordered.iter().enumerate() {
+        let rank = match index {
+            0 => 1,
+            _ if key(ordered[index - 1]) == key(s) => ranks[&ordered[index - 1].player_id],
+            _ => index as i32 + 1,
+        };
+        ranks.insert(s.player_id, rank);
+    }
+    ranks
+}
+
+/// A tournament summary with the place points it is ranked by.
+struct RankedSummary {
+    summary: TournamentSummary,
+    place_point: f32,
+}
+
+/// Builds each tournament's final ranking from the results, ordered by
+/// place. Places go by total place points, then total game points, like the
+/// standings, so that uma and oka move them.
+fn tournament_summaries(
+    results: &[ScoredResult],
+    names: &HashMap<i32, String>,
+    sessions: &HashMap<i32, (i32, String)>,
+) -> HashMap<i32, Vec<RankedSummary>> {
+    // Results arrive in session order, so rounds are pushed in order too
+    let mut rounds: HashMap<(i32, i32), Vec<(i32, RoundPoint)>> = HashMap::new();
+    let mut place_points: HashMap<(i32, i32), f32> = HashMap::new();
+    for result in results {
+        *place_points.entry((result.tournament_id, result.player_id)).or_default() += result.points.place_point;
+        let player_rounds = rounds.entry((result.tournament_id, result.player_id)).or_default();
+        match player_rounds.last_mut() {
+            Some((session_id, round)) if *session_id == result.session_id => {
+                round.table_point += result.points.table_point;
+                round.game_point += result.game_point;
+            }
+            _ => player_rounds.push((
+                result.session_id,
+                RoundPoint {
+                    round_number: sessions[&result.session_id].0,
+                    name: sessions[&result.session_id].1.clone(),
+                    table_point: result.points.table_point,
+                    game_point: result.game_point,
+                },
+            )),
+        }
+    }
+
+    let mut summaries: HashMap<i32, Vec<RankedSummary>> = HashMap::new();
+    for ((tournament_id, player_id), player_rounds) in rounds {
+        let round_point: Vec<RoundPoint> = player_rounds.into_iter().map(|(_, round)| round).collect();
+        summaries.entry(tournament_id).or_default().push(RankedSummary {
+            summary: TournamentSummary {
+                player_id,
+                player_name: names.get(&player_id).cloned().unwrap_or_default(),
+                tournament_place: 0,
+                total_point: TotalPoint {
+                    table_point: round_point.iter().map(|r| r.table_point).sum(),
+                    game_point: round_point.iter().map(|r| r.game_point).sum(),
+                },
+                round_point,
+            },
+            place_point: place_points[&(tournament_id, player_id)],
+        });
+    }
+
+    for ranking in summaries.values_mut() {
+        ranking.sort_by(|a, b| {
+            b.place_point
+                .partial_cmp(&a.place_point)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(b.summary.total_point.game_point.cmp(&a.summary.total_point.game_point))
+                .then(a.summary.player_id.cmp(&b.summary.player_id))
+        });
+        for (index, ranked) in ranking.iter_mut().enumerate() {
+            ranked.summary.tournament_place = index as i32 + 1;
+        }
+    }
+    summaries
+}
+
+/// Recomputes rankings and player stats from the raw game points under
+/// another ruleset, without writing anything. "Current" figures use the
+/// points stored under each tournament's own ruleset.
+#[utoipa::path(
+    post,
+    path = "/v1/what_if",
+    request_body = WhatIfRequest,
+    responses(
+        (status = 200, description = "Rankings under the current rulesets and under the given one", body = WhatIfResponse),
+        (status = 400, description = "Malformed request body", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Tournament not found", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "Ruleset failed validation", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "Unexpected server error", body = Problem, content_type = "application/problem+json")
+    )
+)]
+pub async fn what_if(
+    State(pool): State<PgPool>,
+    Json(request): Json<WhatIfRequest>,
+) -> Result<Json<WhatIfResponse>, AppError> {
+    let mut errors = ValidationErrors::default();
+    request.ruleset.validate(&mut errors, "ruleset.");
+    errors.into_result(())?;
+    let ruleset = request.ruleset.unsaved("what-if");
+
+    let (tournament_id, series) = match &request.scope {
+        WhatIfScope::Tournament { tournament_id } => {
+            let exists = sqlx::query_scalar::<_, bool>("SELECT EXISTS (SELECT 1 FROM tournaments WHERE id = $1)")
+                .bind(tournament_id)
+                .fetch_one(&pool)
+                .await?;
+            if !exists {
+                return Err(AppError::not_found("tournament"));
+            }
+            (Some(*tournament_id), None)
+        }
+        WhatIfScope::Series { sub_name } => (None, Some(sub_name.as_str())),
+        WhatIfScope::AllTime => (None, None),
+    };
+
+    let rows = sqlx::query_as::<_, ResultRow>(
+        r#"
+        SELECT
+            t.id as tournament_id,
+            t.name as tournament_name,
+            t.date as tournament_date,
+            s.id as session_id,
+            s.round_number,
+            s.name as session_name,
+            g.id as game_id,
+            p.id as player_id,
+            p.name as player_name,
+            gpr.seat_wind,
+            gpr.game_point,
+            gpr.table_point,
+            gpr.place_point
+        FROM game_player_results gpr
+        JOIN players p ON p.id = gpr.player_id
+        JOIN games g ON g.id = gpr.game_id
+        JOIN sessions s ON s.id = g.session_id
+        JOIN tournaments t ON t.id = s.tournament_id
+        WHERE ($1::INTEGER IS NULL OR t.id = $1)
+          AND ($2::TEXT IS NULL OR t.sub_name = $2)
+        ORDER BY t.date, t.id, s.round_number, g.id, gpr.id
+        "#
+    )
+    .bind(tournament_id)
+    .bind(series)
+    .fetch_all(&pool)
+    .await?;
+
+    let mut current = Vec::with_capacity(rows.len());
+    let mut new = Vec::with_capacity(rows.len());
+    for game in rows.chunk_by(|a, b| a.game_id == b.game_id) {
+        let stored: Vec<DerivedPoints> = game
+            .iter()
+            .map(|r| DerivedPoints {
+                table_point: r.table_point,
+                place_point: r.place_point,
+            })
+            .collect();
+        let game_points: Vec<i32> = game.iter().map(|r| r.game_point).collect();
+        current.extend(score_game(game, &stored));
+        new.extend(score_game(game, &scoring::derive_points(&ruleset, &game_points)));
+    }
+
+    let names: HashMap<i32, String> = rows.iter().map(|r| (r.player_id, r.player_name.clone())).collect();
+    let sessions: HashMap<i32, (i32, String)> = rows
+        .iter()
+        .map(|r| (r.session_id, (r.round_number, r.session_name.clone())))
+        .collect();
+
+    #[derive(sqlx::FromRow)]
+    struct HandStatsRow {
+        player_id: i32,
+        #[sqlx(flatten)]
+        hands: HandStats,
+    }
+    let hand_stats: HashMap<i32, HandStats> = sqlx::query_as::<_, HandStatsRow>(
+        r#"
+        SELECT player_id, hand_count, agari_rate, houjuu_rate, riichi_rate, call_rate,
+            avg_win_value, avg_deal_in_value, ryuukyoku_tenpai_rate, tsumo_ratio
+        FROM player_stats_filtered(NULL, NULL, $1, $2)
+        WHERE hand_count > 0
+        "#
+    )
+    .bind(tournament_id.map(|id| vec![id]))
+    .bind(series)
+    .fetch_all(&pool)
+    .await?
+    .into_iter()
+    .map(|row| (row.player_id, row.hands))
+    .collect();
+
+    let current_stats = player_stats(&current, &names, &hand_stats);
+    let mut new_stats = player_stats(&new, &names, &hand_stats);
+    let current_ranks = standing_ranks(&current_stats);
+    let new_ranks = standing_ranks(&new_stats);
+    let mut standings: Vec<StandingChange> = current_stats
+        .into_iter()
+        .filter_map(|(player_id, current)| {
+            let new = new_stats.remove(&player_id)?;
+            let (current_rank, new_rank) = (current_ranks[&player_id], new_ranks[&player_id]);
+            Some(StandingChange {
+                player_id,
+                player_name: current.player_name.clone(),
+                current_rank,
+                new_rank,
+                rank_change: current_rank - new_rank,
+                current,
+                new,
+            })
+        })
+        .collect();
+    standings.sort_by_key(|s| (s.new_rank, s.player_id));
+
+    let mut current_summaries = tournament_summaries(&current, &names, &sessions);
+    let mut new_summaries = tournament_summaries(&new, &names, &sessions);
+    let mut tournaments: Vec<TournamentRankingChange> = Vec::new();
+    for row in &rows {
+        if tournaments.last().is_some_and(|t| t.tournament_id == row.tournament_id) {
+            continue;
+        }
+        let mut current_by_player: HashMap<i32, RankedSummary> = current_summaries
+            .remove(&row.tournament_id)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|ranked| (ranked.summary.player_id, ranked))
+            .collect();
+        let rankings = new_summaries
+            .remove(&row.tournament_id)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|new| {
+                let current = current_by_player.remove(&new.summary.player_id)?;
+                Some(SummaryChange {
+                    player_id: new.summary.player_id,
+                    player_name: new.summary.player_name.clone(),
+                    current_place: current.summary.tournament_place,
+                    new_place: new.summary.tournament_place,
+                    place_change: current.summary.tournament_place - new.summary.tournament_place,
+                    current_place_point: current.place_point,
+                    new_place_point: new.place_point,
+                    current: current.summary,
+                    new: new.summary,
+                })
+            })
+            .collect();
+        tournaments.push(TournamentRankingChange {
+            tournament_id: row.tournament_id,
+            tournament_name: row.tournament_name.clone(),
+            tournament_date: row.tournament_date,
+            rankings,
+        });
+    }
+
+    Ok(Json(WhatIfResponse { standings, tournaments }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ruleset::{Ruleset, TieHandling};
+
+    /// Rows of one tournament where players 1-4 sit East to North in every
+    /// game, each game in its own round, stored under the standard ruleset.
+    fn rows(games: &[[i32; 4]]) -> Vec<ResultRow> {
+        let mut rows = Vec::new();
+        for (index, game_points) in games.iter().enumerate() {
+            let stored = scoring::derive_points(&Ruleset::standard(), game_points);
+            for (seat, (&game_point, points)) in game_points.iter().zip(stored).enumerate() {
+                rows.push(ResultRow {
+                    tournament_id: 1,
+                    tournament_name: "test".to_string(),
+                    tournament_date: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+                    session_id: index as i32 + 1,
+                    round_number: index as i32 + 1,
+                    session_name: format!("{}回戦", index + 1),
+                    game_id: index as i32 + 1,
+                    player_id: seat as i32 + 1,
+                    player_name: format!("player {}", seat + 1),
+                    seat_wind: SeatWind::ALL[seat].as_str().to_string(),
+                    game_point,
+                    table_point: points.table_point,
+                    place_point: points.place_point,
+                });
+            }
+        }
+        rows
+    }
+
+    /// Scores every game of `rows` under `ruleset`, as `what_if` does.
+    fn score(rows: &[ResultRow], ruleset: &Ruleset) -> Vec<ScoredResult> {
+        rows.chunk_by(|a, b| a.game_id == b.game_id)
+            .flat_map(|game| {
+                let game_points: Vec<i32> = game.iter().map(|r| r.game_point).collect();
+                score_game(game, &scoring::derive_points(ruleset, &game_points))
+            })
+            .collect()
+    }
+
+    fn with_oka() -> Ruleset {
+        Ruleset {
+            return_points: 30000,
+            ..Ruleset::standard()
+        }
+    }
+
+    fn names(rows: &[ResultRow]) -> HashMap<i32, String> {
+        rows.iter().map(|r| (r.player_id, r.player_name.clone())).collect()
+    }
+
+    fn sessions(rows: &[ResultRow]) -> HashMap<i32, (i32, String)> {
+        rows.iter().map(|r| (r.session_id, (r.round_number, r.session_name.clone()))).collect()
+    }
+
+    /// Player 1 wins the first game and comes last in the second; player 3
+    /// wins the second. Both take one first place, so table points rank
+    /// them the same under any uma.
+    const TWO_GAMES: [[i32; 4]; 2] = [[300, 100, -100, -300], [-300, 100, 150, 50]];
+
+    #[test]
+    fn oka_changes_place_points_but_not_table_points() {
+        let rows = rows(&TWO_GAMES);
+        let standard = score(&rows, &Ruleset::standard());
+        let oka = score(&rows, &with_oka());
+
+        let place_points = |results: &[ScoredResult]| results.iter().map(|r| r.points.place_point).collect::<Vec<_>>();
+        assert_eq!(place_points(&standard), [3.0, 1.0, -1.0, -3.0, -3.0, 1.0, 3.0, -1.0]);
+        assert_eq!(place_points(&oka), [5.0, 1.0, -1.0, -3.0, -3.0, 1.0, 5.0, -1.0]);
+        for (a, b) in standard.iter().zip(&oka) {
+            assert_eq!(a.points.table_point, b.points.table_point);
+            assert_eq!((a.place_from, a.tied_players), (b.place_from, b.tied_players));
+        }
+    }
+
+    #[test]
+    fn tournament_places_move_with_oka() {
+        let rows = rows(&TWO_GAMES);
+        let places = |ruleset: &Ruleset| {
+            let mut summaries = tournament_summaries(&score(&rows, ruleset), &names(&rows), &sessions(&rows));
+            summaries
+                .remove(&1)
+                .unwrap()
+                .into_iter()
+                .map(|ranked| (ranked.summary.player_id, ranked.summary.tournament_place, ranked.place_point))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(places(&Ruleset::standard()), [(2, 1, 2.0), (3, 2, 2.0), (1, 3, 0.0), (4, 4, -4.0)]);
+        assert_eq!(places(&with_oka()), [(3, 1, 4.0), (2, 2, 2.0), (1, 3, 2.0), (4, 4, -4.0)]);
+    }
+
+    #[test]
+    fn summaries_keep_each_round() {
+        let rows = rows(&TWO_GAMES);
+        let summaries = tournament_summaries(&score(&rows, &Ruleset::standard()), &names(&rows), &sessions(&rows));
+        let player_1 = summaries[&1].iter().find(|ranked| ranked.summary.player_id == 1).unwrap();
+        let rounds: Vec<(i32, f32, i32)> =
+            player_1.summary.round_point.iter().map(|r| (r.round_number, r.table_point, r.game_point)).collect();
+        assert_eq!(rounds, [(1, 4.0, 300), (2, 1.0, -300)]);
+        assert_eq!((player_1.summary.total_point.table_point, player_1.summary.total_point.game_point), (5.0, 0));
+    }
+
+    #[test]
+    fn standing_ranks_change_with_oka() {
+        let rows = rows(&TWO_GAMES);
+        let ranks = |ruleset: &Ruleset| {
+            let stats = player_stats(&score(&rows, ruleset), &names(&rows), &HashMap::new());
+            let ranks = standing_ranks(&stats);
+            (1..=4).map(|player| ranks[&player]).collect::<Vec<_>>()
+        };
+        let (current, new) = (ranks(&Ruleset::standard()), ranks(&with_oka()));
+        assert_eq!(current, [3, 1, 2, 4]);
+        assert_eq!(new, [3, 2, 1, 4]);
+        let rank_changes: Vec<i32> = current.iter().zip(&new).map(|(c, n)| c - n).collect();
+        assert_eq!(rank_changes, [0, -1, 1, 0]);
+    }
+
+    #[test]
+    fn split_ties_share_placements_and_ranks() {
+        let rows = rows(&[[100, 100, -100, -100]]);
+        let results = score(&rows, &Ruleset::standard());
+
+        assert_eq!((results[0].place_from, results[0].tied_players), (0, 2));
+        assert_eq!((results[2].place_from, results[2].tied_players), (2, 2));
+        assert_eq!([0, 1, 2, 3].map(|placement| place_credit(&results[0], placement)), [0.5, 0.5, 0.0, 0.0]);
+
+        let stats = player_stats(&results, &names(&rows), &HashMap::new());
+        let player_1 = &stats[&1];
+        assert_eq!((player_1.first_place_count, player_1.second_place_count), (Some(0.5), Some(0.5)));
+        assert_eq!((player_1.total_pp, player_1.shared_place_count), (Some(2.0), Some(1)));
+
+        let ranks = standing_ranks(&stats);
+        assert_eq!((1..=4).map(|player| ranks[&player]).collect::<Vec<_>>(), [1, 1, 3, 3]);
+    }
+
+    #[test]
+    fn seat_order_breaks_ties_by_seat() {
+        let rows = rows(&[[100, 100, -100, -100]]);
+        let seat_order = Ruleset {
+            tie_handling: TieHandling::SeatOrder,
+            ..Ruleset::standard()
+        };
+        let results = score(&rows, &seat_order);
+
+        let placements: Vec<(usize, usize)> = results.iter().map(|r| (r.place_from, r.tied_players)).collect();
+        assert_eq!(placements, [(0, 1), (1, 1), (2, 1), (3, 1)]);
+        let stats = player_stats(&results, &names(&rows), &HashMap::new());
+        assert_eq!((stats[&1].first_place_count, stats[&1].total_pp), (Some(1.0), Some(3.0)));
+        assert_eq!(stats[&2].shared_place_count, Some(0));
+
+        let ranks = standing_ranks(&stats);
+        assert_eq!((1..=4).map(|player| ranks[&player]).collect::<Vec<_>>(), [1, 2, 3, 4]);
+    }
+}
@@ -1,5 +1,9 @@
-import type { ITournament } from '../model'
+import type { ITournament, ITournamentListItem, ITournamentPage } from '../model'
 import { baseApi } from '@/shared/api'
+import type { paths } from '@/shared/api'
+
+type TournamentListItemApiResponse =
+  paths['/v1/tournaments']['get']['responses']['200']['content']['application/json']['items'][number]
 
 type TournamentApiResponse = {
   id: number
@@ -49,8 +53,28 @@ type TournamentDetailApiResponse = {
   }[]
 }
 
-function transformTournamentList(apiTournaments: TournamentDetailApiResponse[]): ITournament[] {
-  return apiTournaments.map((tournament) => transformTournamentDetail(tournament))
+function transformTournamentListItem(apiTournament: TournamentListItemApiResponse): ITournamentListItem {
+  return {
+    id: apiTournament.id,
+    info: {
+      name: apiTournament.info.name,
+      subName: apiTournament.info.sub_name,
+      date: apiTournament.info.date,
+      location: apiTournament.info.location,
+    },
+    winner: apiTournament.winner
+      ? {
+          playerId: apiTournament.winner.player_id,
+          playerName: apiTournament.winner.player_name,
+          totalPoint: {
+            tablePoint: apiTournament.winner.total_point.table_point,
+            gamePoint: apiTournament.winner.total_point.game_point,
+          },
+        }
+      : null,
+    playerCount: apiTournament.player_count,
+    gameCount: apiTournament.game_count,
+  }
 }
 
 function transformTournamentDetail(apiTournament: TournamentDetailApiResponse): ITournament {
@@ -95,14 +119,34 @@ function transformTournamentDetail(apiTournament: TournamentDetailApiResponse):
   }
 }
 
-export const fetchTournaments = async (): Promise<ITournament[]> => {
-  const { data, error } = await baseApi.GET('/v1/tournaments')
+export const fetchTournamentPage = async (cursor?: string, limit?: number): Promise<ITournamentPage> => {
+  const { data, error } = await baseApi.GET('/v1/tournaments', {
+    params: {
+      query: { cursor, limit },
+    },
+  })
 
   if (error) {
     throw new Error(`Failed to fetch tournaments: ${error}`)
   }
 
-  return transformTournamentList(data || [])
+  return {
+    items: data.items.map(transformTournamentListItem),
+    nextCursor: data.next_cursor ?? null,
+  }
+}
+
+// 全ページを順に取得する（大会選択など一覧全体が必要な場合）
+export const fetchTournaments = async (): Promise<ITournamentListItem[]> => {
+  const tournaments: ITournamentListItem[] = []
+  let cursor: string | undefined
+  do {
+    const page = await fetchTournamentPage(cursor, 100)
+    tournaments.push(...page.items)
+    cursor = page.nextCursor ?? undefined
+  } while (cursor)
+
+  return tournaments
 }
 
 export const fetchTournamentById = async (id: string): Promise<ITournament | undefined> => {
@@ -76,3 +76,28 @@ export interface ITournament {
     }[]
   }[]
 }
+
+export interface ITournamentListItem {
+  id: number
+  info: {
+    name: string
+    subName: string
+    date: string
+    location: string
+  }
+  winner: {
+    playerId: number
+    playerName: string
+    totalPoint: {
+      tablePoint: number
+      gamePoint: number
+    }
+  } | null
+  playerCount: number
+  gameCount: number
+}
+
+export interface ITournamentPage {
+  items: ITournamentListItem[]
+  nextCursor: string | null
+}
@@ -0,0 +1,32 @@
+const STORAGE_KEY = 'auth'
+
+type StoredToken = {
+  token: string
+  expiresAt: string
+}
+
+export function getAuthToken(): string | null {
+  const stored = localStorage.getItem(STORAGE_KEY)
+  if (!stored) return null
+
+  const { token, expiresAt } = JSON.parse(stored) as StoredToken
+  if (new Date(expiresAt) <= new Date()) {
+    clearAuthToken()
+    return null
+  }
+  return token
+}
+
+export function setAuthToken(token: string, expiresAt: string) {
+  localStorage.setItem(STORAGE_KEY, JSON.stringify({ token, expiresAt } satisfies StoredToken))
+}
+
+export function clearAuthToken() {
+  localStorage.removeItem(STORAGE_KEY)
+}
+
+// 書き込み系 API は Bearer トークンが必要
+export function authHeaders(): Record<string, string> {
+  const token = getAuthToken()
+  return token ? { Authorization: `Bearer ${token}` } : {}
+}
@@ -1,8 +1,18 @@
 import createClient from 'openapi-fetch'
 import type { paths } from './schema'
+import { getAuthToken } from './auth-token'
 
 export const baseApi =
   createClient<paths>({
     baseUrl: import.meta.env.VITE_API_BASE_URL,
   }) || ''
+
+baseApi.use({
+  onRequest({ request }) {
+    const token = getAuthToken()
+    if (token) request.headers.set('Authorization', `Bearer ${token}`)
+    return request
+  },
+})
+
 export type BaseApi = typeof baseApi
@@ -1,2 +1,3 @@
 export { baseApi } from './base-api'
+export { authHeaders, clearAuthToken, getAuthToken, setAuthToken } from './auth-token'
 export type { paths } from './schema'
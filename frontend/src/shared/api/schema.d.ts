@@ -4,14 +4,14 @@
  */
 
 export interface paths {
-    "/v1/player_stats": {
+    "/health": {
         parameters: {
             query?: never;
             header?: never;
             path?: never;
             cookie?: never;
         };
-        get: operations["get_player_stats_list"];
+        get: operations["health"];
         put?: never;
         post?: never;
         delete?: never;
@@ -20,14 +20,14 @@ export interface paths {
         patch?: never;
         trace?: never;
     };
-    "/v1/player_stats/{player_id}": {
+    "/health/ready": {
         parameters: {
             query?: never;
             header?: never;
             path?: never;
             cookie?: never;
         };
-        get: operations["get_player_stats"];
+        get: operations["health_ready"];
         put?: never;
         post?: never;
         delete?: never;
@@ -36,14 +36,14 @@ export interface paths {
         patch?: never;
         trace?: never;
     };
-    "/v1/tournaments": {
+    "/v1": {
         parameters: {
             query?: never;
             header?: never;
             path?: never;
             cookie?: never;
         };
-        get: operations["get_tournaments"];
+        get: operations["root"];
         put?: never;
         post?: never;
         delete?: never;
@@ -52,281 +52,4036 @@ export interface paths {
         patch?: never;
         trace?: never;
     };
-    "/v1/tournaments/{tournament_id}": {
+    "/v1/auth/login": {
         parameters: {
             query?: never;
             header?: never;
             path?: never;
             cookie?: never;
         };
-        get: operations["get_tournament"];
+        get?: never;
         put?: never;
-        post?: never;
+        post: operations["login"];
         delete?: never;
         options?: never;
         head?: never;
         patch?: never;
         trace?: never;
     };
-}
-export type webhooks = Record<string, never>;
-export interface components {
-    schemas: {
-        GameDetail: {
-            /** Format: int32 */
-            game_id: number;
-            players: components["schemas"]["PlayerGameResult"][];
-            session_name: string;
-            /** Format: date */
-            tournament_date: string;
-            /** Format: int32 */
-            tournament_id: number;
-            tournament_location: string;
-            tournament_name: string;
-            tournament_sub_name: string;
-        };
-        GameInfo: {
-            /** Format: int32 */
-            forfeit_game_point: number;
-            /** Format: int32 */
-            id: number;
-            player_results: components["schemas"]["PlayerGameResult"][];
-        };
-        PlayerGameResult: {
-            /** Format: int32 */
-            game_point: number;
-            /** Format: float */
-            place_point: number;
-            /** Format: int32 */
-            player_id: number;
-            player_name: string;
-            /** Format: float */
-            table_point: number;
-        };
-        PlayerStats: {
-            /** Format: float */
-            avg_gp?: number | null;
-            /** Format: float */
-            avg_pp?: number | null;
-            /** Format: float */
-            avg_tp?: number | null;
-            /** Format: int64 */
-            first_place_count?: number | null;
-            /** Format: float */
-            first_place_ratio?: number | null;
-            /** Format: int64 */
-            fourth_place_count?: number | null;
-            /** Format: float */
-            fourth_place_ratio?: number | null;
-            /** Format: int64 */
-            game_count?: number | null;
-            /** Format: int32 */
-            player_id: number;
-            player_name: string;
-            /** Format: int64 */
-            second_place_count?: number | null;
-            /** Format: float */
-            second_place_ratio?: number | null;
-            /** Format: int64 */
-            third_place_count?: number | null;
-            /** Format: float */
-            third_place_ratio?: number | null;
-            /** Format: int64 */
-            total_gp?: number | null;
-            /** Format: float */
-            total_pp?: number | null;
-            /** Format: float */
-            total_tp?: number | null;
-        };
-        PlayerStatsWithGames: {
-            /** Format: float */
-            avg_gp?: number | null;
-            /** Format: float */
-            avg_pp?: number | null;
-            /** Format: float */
-            avg_tp?: number | null;
-            /** Format: int64 */
-            first_place_count?: number | null;
-            /** Format: float */
-            first_place_ratio?: number | null;
-            /** Format: int64 */
-            fourth_place_count?: number | null;
-            /** Format: float */
-            fourth_place_ratio?: number | null;
-            /** Format: int64 */
-            game_count?: number | null;
-            /** Format: int32 */
-            player_id: number;
-            player_name: string;
-            /** Format: int64 */
-            second_place_count?: number | null;
-            /** Format: float */
-            second_place_ratio?: number | null;
-            /** Format: int64 */
-            third_place_count?: number | null;
-            /** Format: float */
-            third_place_ratio?: number | null;
-            /** Format: int64 */
-            total_gp?: number | null;
-            /** Format: float */
-            total_pp?: number | null;
-            /** Format: float */
-            total_tp?: number | null;
-        } & {
-            game_details: components["schemas"]["GameDetail"][];
-        };
-        RoundPoint: {
-            /** Format: int32 */
-            game_point: number;
-            /** Format: float */
-            table_point: number;
-        };
-        SessionDetail: {
-            games: components["schemas"]["GameInfo"][];
-            info: components["schemas"]["SessionInfo"];
-        };
-        SessionInfo: {
-            /** Format: int32 */
-            id: number;
-            name: string;
-        };
-        TotalPoint: {
-            /** Format: int32 */
-            game_point: number;
-            /** Format: float */
-            table_point: number;
-        };
-        Tournament: {
-            /** Format: date */
-            date: string;
-            /** Format: int32 */
-            id: number;
-            location: string;
-            name: string;
-            sub_name: string;
-        };
-        TournamentDetail: {
-            /** Format: int32 */
-            id: number;
-            info: components["schemas"]["Tournament"];
-            sessions: components["schemas"]["SessionDetail"][];
-            summary: components["schemas"]["TournamentSummary"][];
-        };
-        TournamentSummary: {
-            /** Format: int32 */
-            player_id: number;
-            player_name: string;
-            round_point: components["schemas"]["RoundPoint"][];
-            total_point: components["schemas"]["TotalPoint"];
-            /** Format: int32 */
-            tournament_place: number;
-        };
-    };
-    responses: never;
-    parameters: never;
-    requestBodies: never;
-    headers: never;
-    pathItems: never;
-}
-export type $defs = Record<string, never>;
-export interface operations {
-    get_player_stats_list: {
+    "/v1/auth/me": {
         parameters: {
             query?: never;
             header?: never;
             path?: never;
             cookie?: never;
         };
-        requestBody?: never;
-        responses: {
-            /** @description 玩家统计 */
-            200: {
-                headers: {
-                    [name: string]: unknown;
-                };
-                content: {
-                    "application/json": components["schemas"]["PlayerStats"][];
-                };
-            };
-        };
+        get: operations["me"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
     };
-    get_player_stats: {
+    "/v1/games": {
         parameters: {
             query?: never;
             header?: never;
-            path: {
-                /** @description 玩家ID */
-                player_id: number;
-            };
+            path?: never;
             cookie?: never;
         };
-        requestBody?: never;
-        responses: {
-            /** @description 玩家统计和比赛详情 */
-            200: {
-                headers: {
-                    [name: string]: unknown;
-                };
-                content: {
-                    "application/json": components["schemas"]["PlayerStatsWithGames"];
-                };
-            };
-            /** @description 玩家未找到 */
-            404: {
-                headers: {
-                    [name: string]: unknown;
-                };
-                content?: never;
-            };
-        };
+        get?: never;
+        put?: never;
+        post: operations["create_game"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
     };
-    get_tournaments: {
+    "/v1/games/{game_id}": {
         parameters: {
             query?: never;
             header?: never;
             path?: never;
             cookie?: never;
         };
-        requestBody?: never;
-        responses: {
-            /** @description Tournament list with details */
-            200: {
-                headers: {
-                    [name: string]: unknown;
-                };
-                content: {
-                    "application/json": components["schemas"]["TournamentDetail"][];
-                };
-            };
-        };
+        get: operations["get_game"];
+        put: operations["update_game"];
+        post?: never;
+        delete: operations["delete_game"];
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
     };
-    get_tournament: {
+    "/v1/player_stats": {
         parameters: {
             query?: never;
             header?: never;
-            path: {
-                /** @description Tournament ID */
-                tournament_id: number;
-            };
+            path?: never;
             cookie?: never;
         };
-        requestBody?: never;
-        responses: {
-            /** @description Tournament detail */
-            200: {
-                headers: {
-                    [name: string]: unknown;
-                };
-                content: {
-                    "application/json": components["schemas"]["TournamentDetail"];
-                };
-            };
+        get: operations["get_player_stats_list"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/v1/player_stats/{player_id}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["get_player_stats"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/v1/players": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["get_players"];
+        put?: never;
+        post: operations["create_player"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/v1/players/{player_id}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post?: never;
+        delete: operations["delete_player"];
+        options?: never;
+        head?: never;
+        patch: operations["update_player"];
+        trace?: never;
+    };
+    "/v1/players/{player_id}/opponents": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["get_opponent_summary"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/v1/players/{player_id}/ratings": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["get_rating_history"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/v1/players/{player_id}/vs/{opponent_id}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["get_head_to_head"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/v1/ratings": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["get_rating_leaderboard"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/v1/rulesets": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["get_rulesets"];
+        put?: never;
+        post: operations["create_ruleset"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/v1/seasons": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["get_seasons"];
+        put?: never;
+        post: operations["create_season"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/v1/seasons/{season_id}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post?: never;
+        delete: operations["delete_season"];
+        options?: never;
+        head?: never;
+        patch: operations["update_season"];
+        trace?: never;
+    };
+    "/v1/seasons/{season_id}/standings": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["get_season_standings"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/v1/sessions/{session_id}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post?: never;
+        delete: operations["delete_session"];
+        options?: never;
+        head?: never;
+        patch: operations["update_session"];
+        trace?: never;
+    };
+    "/v1/tools/evaluate": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post: operations["evaluate_hand"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/v1/tools/score": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post: operations["calculate_score"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/v1/tournaments": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["get_tournaments"];
+        put?: never;
+        post: operations["create_tournament"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/v1/tournaments/{tournament_id}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["get_tournament"];
+        put?: never;
+        post?: never;
+        delete: operations["delete_tournament"];
+        options?: never;
+        head?: never;
+        patch: operations["update_tournament"];
+        trace?: never;
+    };
+    "/v1/tournaments/{tournament_id}/sessions": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["get_sessions"];
+        put?: never;
+        post: operations["create_session"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/v1/tournaments/{tournament_id}/sessions/order": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put: operations["reorder_sessions"];
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/v1/users": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["get_users"];
+        put?: never;
+        post: operations["create_user"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/v1/what_if": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post: operations["what_if"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+}
+export type webhooks = Record<string, never>;
+export interface components {
+    schemas: {
+        /** @description The signed-in user, taken from a `Bearer` token. */
+        AuthUser: {
+            /** Format: int32 */
+            id: number;
+            role: components["schemas"]["Role"];
+            username: string;
+        };
+        CreateGameRequest: {
+            /** Format: int32 */
+            forfeit_game_point?: number | null;
+            /**
+             * @description Optional hand-by-hand log. It must add up to the game points and
+             *     `forfeit_game_point`; replacing a game replaces its log too
+             */
+            hands?: components["schemas"]["Hand"][] | null;
+            player_results: components["schemas"]["CreatePlayerResultRequest"][];
+            /**
+             * Format: int32
+             * @description The round to record the game in. When it does not exist yet it is
+             *     created, named `session_name` or "N回戦"
+             */
+            round_number?: number | null;
+            /**
+             * @description Identifies the round when `round_number` is left out; a new name
+             *     creates a new round after the last one. Surrounding spaces are ignored
+             */
+            session_name?: string | null;
+            /**
+             * Format: int32
+             * @description Table the game was played at, from 1
+             */
+            table_no?: number | null;
+            /** Format: int32 */
+            tournament_id: number;
+        };
+        CreatePlayerRequest: {
+            name: string;
+        };
+        CreatePlayerResultRequest: {
+            /** Format: int32 */
+            game_point: number;
+            /** Format: int32 */
+            player_id: number;
+            seat_wind?: null | components["schemas"]["SeatWind"];
+            /**
+             * Format: float
+             * @description Optional; derived from `game_point` on the server. When sent it must
+             *     match the derived value.
+             */
+            table_point?: number | null;
+        };
+        CreateRulesetRequest: components["schemas"]["RulesetDefinition"] & {
+            name: string;
+        };
+        CreateSeasonRequest: {
+            /** Format: int32 */
+            drop_worst?: number;
+            league_points?: number[];
+            /** Format: int32 */
+            min_attendance?: number;
+            name: string;
+            scoring?: components["schemas"]["SeasonScoring"];
+            tournament_ids?: number[];
+        };
+        CreateSessionRequest: {
+            /** @description Defaults to "N回戦" */
+            name?: string | null;
+            /**
+             * Format: int32
+             * @description Defaults to the round after the last one
+             */
+            round_number?: number | null;
+        };
+        CreateTournamentRequest: {
+            /** Format: date */
+            date: string;
+            location: string;
+            name: string;
+            /**
+             * Format: int32
+             * @description Defaults to the first ruleset
+             */
+            ruleset_id?: number | null;
+            sub_name: string;
+        };
+        CreateUserRequest: {
+            password: string;
+            role: components["schemas"]["Role"];
+            username: string;
+        };
+        EvaluateRequest: {
+            /** @description Won on a tile added to another player's kan */
+            chankan?: boolean;
+            chiihou?: boolean;
+            double_riichi?: boolean;
+            /** Format: int32 */
+            honba?: number;
+            ippatsu?: boolean;
+            /** @description Round 4 han 30 fu and 3 han 60 fu up to a mangan */
+            kiriage_mangan?: boolean;
+            /** @description Won on the last tile of the wall (haitei) or its discard (houtei) */
+            last_tile?: boolean;
+            riichi?: boolean;
+            /**
+             * Format: int32
+             * @description Riichi deposits the winner collects from the table
+             */
+            riichi_sticks?: number;
+            /** @description Won on the replacement tile after a kan */
+            rinshan?: boolean;
+            round_wind: components["schemas"]["SeatWind"];
+            seat_wind: components["schemas"]["SeatWind"];
+            tenhou?: boolean;
+            win: components["schemas"]["WinType"];
+        } & {
+            dora_indicators?: string;
+            /** @description Concealed tiles, winning tile included, e.g. `123m456p789s1122z` */
+            hand: string;
+            melds?: components["schemas"]["MeldRequest"][];
+            /** @description Only counted with riichi */
+            ura_dora_indicators?: string;
+            /** @description One tile of `hand`, e.g. `2z` */
+            winning_tile: string;
+        };
+        Evaluation: {
+            best?: null | components["schemas"]["Interpretation"];
+            /** @description Whether the tiles form a winning shape, with or without a yaku */
+            complete: boolean;
+            interpretations: components["schemas"]["Interpretation"][];
+        };
+        GameDetail: {
+            /** Format: int32 */
+            game_id: number;
+            players: components["schemas"]["PlayerGameResult"][];
+            /** Format: int32 */
+            round_number: number;
+            session_name: string;
+            /** Format: date */
+            tournament_date: string;
+            /** Format: int32 */
+            tournament_id: number;
+            tournament_location: string;
+            tournament_name: string;
+            tournament_sub_name: string;
+        };
+        GameInfo: {
+            /** Format: int32 */
+            forfeit_game_point: number;
+            /** Format: int32 */
+            id: number;
+            player_results: components["schemas"]["PlayerGameResult"][];
+            /**
+             * Format: int32
+             * @description Absent for games recorded before table numbers were kept
+             */
+            table_no?: number | null;
+        };
+        GameRecord: {
+            /** Format: int32 */
+            forfeit_game_point: number;
+            /** @description Hand-by-hand log; empty when none was recorded */
+            hands: components["schemas"]["Hand"][];
+            /** Format: int32 */
+            id: number;
+            player_results: components["schemas"]["PlayerGameResult"][];
+            session: components["schemas"]["SessionInfo"];
+            /** Format: int32 */
+            table_no?: number | null;
+            /** Format: int32 */
+            tournament_id: number;
+        };
+        Group: {
+            kind: components["schemas"]["GroupKind"];
+            /** @description Called from another player's discard */
+            open: boolean;
+            tiles: string;
+            /** @description Completed by the winning tile */
+            winning: boolean;
+        };
+        /** @enum {string} */
+        GroupKind: "pair" | "sequence" | "triplet" | "quad" | "orphans";
+        /**
+         * @description One hand (kyoku) of a game. Seats are the players' starting winds; the
+         *     dealer of hand N is the player who started in seat N, so East deals
+         *     hand 1 of every round.
+         */
+        Hand: {
+            /** @description Players who called a tile (chi, pon or open kan) during the hand */
+            called?: components["schemas"]["SeatWind"][];
+            chombo?: null | components["schemas"]["SeatWind"];
+            discarder?: null | components["schemas"]["SeatWind"];
+            /**
+             * Format: int32
+             * @description 1 to 4 within the round
+             */
+            hand_number: number;
+            /** Format: int32 */
+            honba?: number;
+            outcome: components["schemas"]["HandOutcome"];
+            /**
+             * @description Net change of each seat's score in points, East first. Includes
+             *     riichi deposits paid and collected.
+             */
+            point_changes: number[];
+            /** @description Players who declared riichi during the hand */
+            riichi?: components["schemas"]["SeatWind"][];
+            /**
+             * Format: int32
+             * @description Riichi deposits on the table when the hand starts
+             */
+            riichi_sticks?: number;
+            round_wind: components["schemas"]["SeatWind"];
+            /** @description Players tenpai at an exhaustive draw */
+            tenpai?: components["schemas"]["SeatWind"][];
+            /** @description Winners with their han and fu; only for ron and tsumo */
+            wins?: components["schemas"]["HandWin"][];
+        };
+        /** @enum {string} */
+        HandForm: "standard" | "seven_pairs" | "thirteen_orphans";
+        /**
+         * @description How a hand ended.
+         * @enum {string}
+         */
+        HandOutcome: "ron" | "tsumo" | "draw" | "abortive_draw" | "chombo";
+        /**
+         * @description Figures from the games recorded with a hand log; absent for players
+         *     without any. Rates are percentages of the hands the player sat in, and
+         *     win and deal-in values leave out riichi deposits.
+         */
+        HandStats: {
+            /** Format: float */
+            agari_rate?: number | null;
+            /** Format: float */
+            avg_deal_in_value?: number | null;
+            /** Format: float */
+            avg_win_value?: number | null;
+            /**
+             * Format: float
+             * @description Hands in which the player called a tile
+             */
+            call_rate?: number | null;
+            /** Format: int64 */
+            hand_count?: number | null;
+            /**
+             * Format: float
+             * @description Hands the player dealt into, i.e. discarded a winning tile
+             */
+            houjuu_rate?: number | null;
+            /** Format: float */
+            riichi_rate?: number | null;
+            /**
+             * Format: float
+             * @description Share of exhaustive draws in which the player was tenpai
+             */
+            ryuukyoku_tenpai_rate?: number | null;
+            /**
+             * Format: float
+             * @description Share of the player's wins that were self-drawn
+             */
+            tsumo_ratio?: number | null;
+        };
+        /**
+         * @description What a hand is worth before anyone pays: the base points every payment
+         *     is a multiple of.
+         */
+        HandValue: {
+            /** Format: int32 */
+            base_points: number;
+            /**
+             * Format: int32
+             * @description Rounded up to the next 10, except 25 for seven pairs; absent when a
+             *     limit makes fu irrelevant and none was given
+             */
+            fu?: number | null;
+            /** Format: int32 */
+            han: number;
+            limit?: null | components["schemas"]["Limit"];
+            /** Format: int32 */
+            yakuman?: number | null;
+        };
+        HandWin: {
+            /**
+             * Format: int32
+             * @description May be left out from 5 han, where fu does not matter
+             */
+            fu?: number | null;
+            /** Format: int32 */
+            han: number;
+            seat_wind: components["schemas"]["SeatWind"];
+            /**
+             * Format: int32
+             * @description Number of yakuman for a yakuman hand; overrides han
+             */
+            yakuman?: number | null;
+        };
+        HeadToHead: {
+            /**
+             * Format: int64
+             * @description Games in which the player finished above the opponent
+             */
+            above_count: number;
+            /**
+             * Format: float
+             * @description Player's game points minus the opponent's, averaged
+             */
+            avg_game_point_difference?: number | null;
+            /** Format: float */
+            avg_opponent_place?: number | null;
+            /** Format: float */
+            avg_place?: number | null;
+            /**
+             * Format: float
+             * @description Opponent's place minus the player's, averaged; positive when the
+             *     player usually finishes higher
+             */
+            avg_place_difference?: number | null;
+            /**
+             * Format: int64
+             * @description Games in which the opponent finished above the player
+             */
+            below_count: number;
+            /** Format: int64 */
+            game_count: number;
+            /** Format: int32 */
+            opponent_id: number;
+            opponent_name: string;
+            /** Format: int64 */
+            tie_count: number;
+        } & {
+            games: components["schemas"]["GameDetail"][];
+            player: components["schemas"]["Player"];
+            tournaments: components["schemas"]["HeadToHeadTournament"][];
+        };
+        HeadToHeadTournament: {
+            /** Format: int64 */
+            game_count: number;
+            /** Format: date */
+            tournament_date: string;
+            /** Format: int32 */
+            tournament_id: number;
+            tournament_name: string;
+        };
+        /** @description One way of reading the hand, scored. */
+        Interpretation: {
+            /** Format: int32 */
+            aka_dora: number;
+            /** Format: int32 */
+            dora: number;
+            form: components["schemas"]["HandForm"];
+            /**
+             * Format: int32
+             * @description Absent for thirteen orphans
+             */
+            fu?: number | null;
+            groups: components["schemas"]["Group"][];
+            /**
+             * Format: int32
+             * @description Yaku and dora; 0 without a yaku, as such a hand cannot win
+             */
+            han: number;
+            payments?: null | components["schemas"]["Payments"];
+            /** Format: int32 */
+            ura_dora: number;
+            value?: null | components["schemas"]["HandValue"];
+            wait: components["schemas"]["Wait"];
+            yaku: components["schemas"]["YakuHan"][];
+        };
+        /** @enum {string} */
+        Limit: "mangan" | "haneman" | "baiman" | "sanbaiman" | "yakuman";
+        LoginRequest: {
+            password: string;
+            username: string;
+        };
+        LoginResponse: {
+            /** Format: date-time */
+            expires_at: string;
+            token: string;
+            user: components["schemas"]["AuthUser"];
+        };
+        /** @enum {string} */
+        MeldKind: "chi" | "pon" | "kan" | "closed_kan";
+        MeldRequest: {
+            kind: components["schemas"]["MeldKind"];
+            /** @description e.g. `555z`, or `406p` with a red five */
+            tiles: string;
+        };
+        /**
+         * @description How a player fared against one opponent in the games they shared.
+         *     Placements come from table points, so a shared placement counts as the
+         *     average of the places it spans (e.g. 2.5 for a tie for 2nd).
+         */
+        OpponentRecord: {
+            /**
+             * Format: int64
+             * @description Games in which the player finished above the opponent
+             */
+            above_count: number;
+            /**
+             * Format: float
+             * @description Player's game points minus the opponent's, averaged
+             */
+            avg_game_point_difference?: number | null;
+            /** Format: float */
+            avg_opponent_place?: number | null;
+            /** Format: float */
+            avg_place?: number | null;
+            /**
+             * Format: float
+             * @description Opponent's place minus the player's, averaged; positive when the
+             *     player usually finishes higher
+             */
+            avg_place_difference?: number | null;
+            /**
+             * Format: int64
+             * @description Games in which the opponent finished above the player
+             */
+            below_count: number;
+            /** Format: int64 */
+            game_count: number;
+            /** Format: int32 */
+            opponent_id: number;
+            opponent_name: string;
+            /** Format: int64 */
+            tie_count: number;
+        };
+        OpponentSummary: {
+            /** @description Opponents the player finishes furthest above, best first */
+            best: components["schemas"]["OpponentRecord"][];
+            player: components["schemas"]["Player"];
+            /** @description Opponents the player finishes furthest below, worst first */
+            worst: components["schemas"]["OpponentRecord"][];
+        };
+        /**
+         * @description Who pays the winner what. Payments include honba; riichi deposits come
+         *     from the table.
+         */
+        Payments: {
+            /**
+             * Format: int32
+             * @description Non-dealer tsumo: paid by the dealer
+             */
+            from_dealer?: number | null;
+            /**
+             * Format: int32
+             * @description Tsumo: paid by each non-dealer
+             */
+            from_each_non_dealer?: number | null;
+            /**
+             * Format: int32
+             * @description The hand's value, without honba or deposits
+             */
+            hand_points: number;
+            /** Format: int32 */
+            honba_points: number;
+            /** Format: int32 */
+            riichi_deposits: number;
+            /**
+             * Format: int32
+             * @description Ron: paid by the discarder
+             */
+            ron?: number | null;
+            /**
+             * Format: int32
+             * @description Everything the winner receives
+             */
+            total: number;
+        };
+        Player: {
+            /** Format: int32 */
+            id: number;
+            name: string;
+        };
+        PlayerGameResult: {
+            /** Format: int32 */
+            game_point: number;
+            /** Format: float */
+            place_point: number;
+            /** Format: int32 */
+            player_id: number;
+            player_name: string;
+            seat_wind: components["schemas"]["SeatWind"];
+            /** Format: float */
+            table_point: number;
+        };
+        PlayerStats: {
+            /** Format: float */
+            agari_rate?: number | null;
+            /** Format: float */
+            avg_deal_in_value?: number | null;
+            /** Format: float */
+            avg_win_value?: number | null;
+            /**
+             * Format: float
+             * @description Hands in which the player called a tile
+             */
+            call_rate?: number | null;
+            /** Format: int64 */
+            hand_count?: number | null;
+            /**
+             * Format: float
+             * @description Hands the player dealt into, i.e. discarded a winning tile
+             */
+            houjuu_rate?: number | null;
+            /** Format: float */
+            riichi_rate?: number | null;
+            /**
+             * Format: float
+             * @description Share of exhaustive draws in which the player was tenpai
+             */
+            ryuukyoku_tenpai_rate?: number | null;
+            /**
+             * Format: float
+             * @description Share of the player's wins that were self-drawn
+             */
+            tsumo_ratio?: number | null;
+        } & {
+            /** Format: float */
+            avg_gp?: number | null;
+            /** Format: float */
+            avg_pp?: number | null;
+            /** Format: float */
+            avg_tp?: number | null;
+            /** Format: float */
+            east_avg_gp?: number | null;
+            /** Format: float */
+            east_avg_place?: number | null;
+            /**
+             * Format: int64
+             * @description Per-seat figures by starting wind; places come from table points,
+             *     so a shared 2nd counts as 2.5
+             */
+            east_game_count?: number | null;
+            /**
+             * Format: float
+             * @description Place counts credit tied placements fractionally: players tied over
+             *     several placements share each of them equally, so two players tied
+             *     for 1st get 0.5 of a 1st and 0.5 of a 2nd each
+             */
+            first_place_count?: number | null;
+            /** Format: float */
+            first_place_ratio?: number | null;
+            /** Format: float */
+            fourth_place_count?: number | null;
+            /** Format: float */
+            fourth_place_ratio?: number | null;
+            /** Format: int64 */
+            game_count?: number | null;
+            /** Format: float */
+            north_avg_gp?: number | null;
+            /** Format: float */
+            north_avg_place?: number | null;
+            /** Format: int64 */
+            north_game_count?: number | null;
+            /** Format: int32 */
+            player_id: number;
+            player_name: string;
+            /** Format: float */
+            second_place_count?: number | null;
+            /** Format: float */
+            second_place_ratio?: number | null;
+            /**
+             * Format: int64
+             * @description Games in which the player's placement was shared with someone else
+             */
+            shared_place_count?: number | null;
+            /** Format: float */
+            south_avg_gp?: number | null;
+            /** Format: float */
+            south_avg_place?: number | null;
+            /** Format: int64 */
+            south_game_count?: number | null;
+            /** Format: float */
+            third_place_count?: number | null;
+            /** Format: float */
+            third_place_ratio?: number | null;
+            /** Format: int64 */
+            total_gp?: number | null;
+            /** Format: float */
+            total_pp?: number | null;
+            /** Format: float */
+            total_tp?: number | null;
+            /** Format: float */
+            west_avg_gp?: number | null;
+            /** Format: float */
+            west_avg_place?: number | null;
+            /** Format: int64 */
+            west_game_count?: number | null;
+        };
+        /** @enum {string} */
+        PlayerStatsSort: "player_id" | "player_name" | "game_count" | "total_gp" | "total_tp" | "total_pp" | "first_place_count" | "second_place_count" | "third_place_count" | "fourth_place_count" | "shared_place_count" | "avg_gp" | "avg_tp" | "avg_pp" | "first_place_ratio" | "second_place_ratio" | "third_place_ratio" | "fourth_place_ratio" | "east_game_count" | "east_avg_place" | "east_avg_gp" | "south_game_count" | "south_avg_place" | "south_avg_gp" | "west_game_count" | "west_avg_place" | "west_avg_gp" | "north_game_count" | "north_avg_place" | "north_avg_gp" | "hand_count" | "agari_rate" | "houjuu_rate" | "riichi_rate" | "call_rate" | "avg_win_value" | "avg_deal_in_value" | "ryuukyoku_tenpai_rate" | "tsumo_ratio";
+        PlayerStatsWithGames: {
+            /** Format: float */
+            agari_rate?: number | null;
+            /** Format: float */
+            avg_deal_in_value?: number | null;
+            /** Format: float */
+            avg_win_value?: number | null;
+            /**
+             * Format: float
+             * @description Hands in which the player called a tile
+             */
+            call_rate?: number | null;
+            /** Format: int64 */
+            hand_count?: number | null;
+            /**
+             * Format: float
+             * @description Hands the player dealt into, i.e. discarded a winning tile
+             */
+            houjuu_rate?: number | null;
+            /** Format: float */
+            riichi_rate?: number | null;
+            /**
+             * Format: float
+             * @description Share of exhaustive draws in which the player was tenpai
+             */
+            ryuukyoku_tenpai_rate?: number | null;
+            /**
+             * Format: float
+             * @description Share of the player's wins that were self-drawn
+             */
+            tsumo_ratio?: number | null;
+        } & {
+            /** Format: float */
+            avg_gp?: number | null;
+            /** Format: float */
+            avg_pp?: number | null;
+            /** Format: float */
+            avg_tp?: number | null;
+            /** Format: float */
+            east_avg_gp?: number | null;
+            /** Format: float */
+            east_avg_place?: number | null;
+            /**
+             * Format: int64
+             * @description Per-seat figures by starting wind; places come from table points,
+             *     so a shared 2nd counts as 2.5
+             */
+            east_game_count?: number | null;
+            /**
+             * Format: float
+             * @description Place counts credit tied placements fractionally: players tied over
+             *     several placements share each of them equally, so two players tied
+             *     for 1st get 0.5 of a 1st and 0.5 of a 2nd each
+             */
+            first_place_count?: number | null;
+            /** Format: float */
+            first_place_ratio?: number | null;
+            /** Format: float */
+            fourth_place_count?: number | null;
+            /** Format: float */
+            fourth_place_ratio?: number | null;
+            /** Format: int64 */
+            game_count?: number | null;
+            /** Format: float */
+            north_avg_gp?: number | null;
+            /** Format: float */
+            north_avg_place?: number | null;
+            /** Format: int64 */
+            north_game_count?: number | null;
+            /** Format: int32 */
+            player_id: number;
+            player_name: string;
+            /** Format: float */
+            second_place_count?: number | null;
+            /** Format: float */
+            second_place_ratio?: number | null;
+            /**
+             * Format: int64
+             * @description Games in which the player's placement was shared with someone else
+             */
+            shared_place_count?: number | null;
+            /** Format: float */
+            south_avg_gp?: number | null;
+            /** Format: float */
+            south_avg_place?: number | null;
+            /** Format: int64 */
+            south_game_count?: number | null;
+            /** Format: float */
+            third_place_count?: number | null;
+            /** Format: float */
+            third_place_ratio?: number | null;
+            /** Format: int64 */
+            total_gp?: number | null;
+            /** Format: float */
+            total_pp?: number | null;
+            /** Format: float */
+            total_tp?: number | null;
+            /** Format: float */
+            west_avg_gp?: number | null;
+            /** Format: float */
+            west_avg_place?: number | null;
+            /** Format: int64 */
+            west_game_count?: number | null;
+        } & {
+            game_details: components["schemas"]["GameDetail"][];
+        };
+        /** @description RFC 7807 problem details body returned for every error response. */
+        Problem: {
+            /** @description Machine-readable error code, e.g. `not_found` or `validation_failed` */
+            code: string;
+            detail?: string | null;
+            /** Format: int32 */
+            status: number;
+            title: string;
+            /** @description URI reference identifying the problem type */
+            type: string;
+            /** @description Individual violations, present for `validation_failed` */
+            violations?: components["schemas"]["Violation"][];
+        };
+        RatingHistoryEntry: {
+            /** Format: double */
+            deviation: number;
+            /** Format: int32 */
+            game_id: number;
+            /**
+             * Format: double
+             * @description Rating after the game
+             */
+            rating: number;
+            /** Format: double */
+            rating_change: number;
+            session_name: string;
+            /** Format: date */
+            tournament_date: string;
+            /** Format: int32 */
+            tournament_id: number;
+            tournament_name: string;
+            /** Format: double */
+            volatility: number;
+        };
+        RatingLeaderboardEntry: {
+            /**
+             * Format: double
+             * @description `rating - 2 * deviation`: the rating the player is very likely to be
+             *     at least as good as. The leaderboard is ordered by it, so a few lucky
+             *     games with a wide deviation do not top it
+             */
+            conservative_rating: number;
+            /** Format: double */
+            deviation: number;
+            /** Format: int64 */
+            game_count: number;
+            /** Format: date */
+            last_played: string;
+            /** Format: int32 */
+            player_id: number;
+            player_name: string;
+            /** Format: double */
+            rating: number;
+            /** Format: double */
+            volatility: number;
+        };
+        ReorderSessionsRequest: {
+            /**
+             * @description Every session of the tournament, in their new order; they are
+             *     renumbered from 1
+             */
+            session_ids: number[];
+        };
+        /**
+         * @description Account roles, ordered so that a higher role includes every lower one.
+         * @enum {string}
+         */
+        Role: "viewer" | "scorekeeper" | "admin";
+        /**
+         * @description A player's points in one round (session) of a tournament. Rounds the
+         *     player sat out are left out, so use `round_number` rather than position.
+         */
+        RoundPoint: {
+            /** Format: int32 */
+            game_point: number;
+            name: string;
+            /** Format: int32 */
+            round_number: number;
+            /** Format: float */
+            table_point: number;
+        };
+        /**
+         * @description Scoring rules a tournament is played under. Place points are the uma for
+         *     the finishing position, plus the oka for first place, in units of
+         *     [`POINTS_PER_PLACE_POINT`]; starting and return points are plain points.
+         */
+        Ruleset: {
+            /** Format: int32 */
+            id: number;
+            /** @description Round 4 han 30 fu and 3 han 60 fu up to a mangan */
+            kiriage_mangan: boolean;
+            name: string;
+            /**
+             * Format: int32
+             * @description Points each player is measured against, e.g. 30000
+             */
+            return_points: number;
+            /**
+             * Format: int32
+             * @description Points each player starts with, e.g. 25000
+             */
+            starting_points: number;
+            tie_handling: components["schemas"]["TieHandling"];
+            /** @description Place points for 1st to 4th */
+            uma: number[];
+        };
+        /**
+         * @description The scoring rules of a ruleset without its identity, as submitted by
+         *     clients.
+         */
+        RulesetDefinition: {
+            kiriage_mangan?: boolean;
+            /**
+             * Format: int32
+             * @description Defaults to `starting_points`, i.e. no oka
+             */
+            return_points?: number | null;
+            /** Format: int32 */
+            starting_points?: number;
+            tie_handling?: components["schemas"]["TieHandling"];
+            /** @description Place points for 1st to 4th */
+            uma: number[];
+        };
+        ScoreRequest: {
+            /** @description Whether the winner is the dealer */
+            dealer?: boolean;
+            /**
+             * Format: int32
+             * @description Needed below 5 han; rounded up to the next 10 except for 25
+             */
+            fu?: number | null;
+            /** Format: int32 */
+            han: number;
+            /** Format: int32 */
+            honba?: number;
+            /** @description Round 4 han 30 fu and 3 han 60 fu up to a mangan */
+            kiriage_mangan?: boolean;
+            /**
+             * Format: int32
+             * @description Riichi deposits the winner collects from the table
+             */
+            riichi_sticks?: number;
+            win: components["schemas"]["WinType"];
+            /**
+             * Format: int32
+             * @description Number of yakuman, e.g. 2 for a double yakuman; overrides han
+             */
+            yakuman?: number | null;
+        };
+        ScoreResult: {
+            /** Format: int32 */
+            base_points: number;
+            /**
+             * Format: int32
+             * @description Rounded up to the next 10, except 25 for seven pairs; absent when a
+             *     limit makes fu irrelevant and none was given
+             */
+            fu?: number | null;
+            /** Format: int32 */
+            han: number;
+            limit?: null | components["schemas"]["Limit"];
+            /** Format: int32 */
+            yakuman?: number | null;
+        } & {
+            /**
+             * Format: int32
+             * @description Non-dealer tsumo: paid by the dealer
+             */
+            from_dealer?: number | null;
+            /**
+             * Format: int32
+             * @description Tsumo: paid by each non-dealer
+             */
+            from_each_non_dealer?: number | null;
+            /**
+             * Format: int32
+             * @description The hand's value, without honba or deposits
+             */
+            hand_points: number;
+            /** Format: int32 */
+            honba_points: number;
+            /** Format: int32 */
+            riichi_deposits: number;
+            /**
+             * Format: int32
+             * @description Ron: paid by the discarder
+             */
+            ron?: number | null;
+            /**
+             * Format: int32
+             * @description Everything the winner receives
+             */
+            total: number;
+        };
+        Season: {
+            /**
+             * Format: int32
+             * @description Each player's lowest-scoring tournaments left out of their total;
+             *     tournaments they missed are the first to go
+             */
+            drop_worst: number;
+            /** Format: int32 */
+            id: number;
+            /**
+             * @description Points for 1st, 2nd, ... place under `league_points` scoring; places
+             *     past the end earn nothing
+             */
+            league_points: number[];
+            /**
+             * Format: int32
+             * @description Tournaments a player must attend to be ranked
+             */
+            min_attendance: number;
+            name: string;
+            scoring: components["schemas"]["SeasonScoring"];
+            /** @description In date order */
+            tournament_ids: number[];
+        };
+        /**
+         * @description What a player earns from each tournament of a season.
+         * @enum {string}
+         */
+        SeasonScoring: "table_points" | "league_points";
+        SeasonStanding: {
+            /** Format: int32 */
+            attendance: number;
+            /**
+             * Format: int32
+             * @description `None` for players below the minimum attendance
+             */
+            place?: number | null;
+            /** Format: int32 */
+            player_id: number;
+            player_name: string;
+            /** @description In date order */
+            results: components["schemas"]["SeasonTournamentResult"][];
+            /**
+             * Format: int32
+             * @description Game points from the results that count; breaks ties in `total_points`
+             */
+            total_game_point: number;
+            /**
+             * Format: float
+             * @description Points from the results that count
+             */
+            total_points: number;
+        };
+        SeasonStandings: {
+            season: components["schemas"]["Season"];
+            /** @description Ranked players first, then unranked ones by points */
+            standings: components["schemas"]["SeasonStanding"][];
+        };
+        SeasonTournamentResult: {
+            /** @description Left out of the total by `drop_worst` */
+            dropped: boolean;
+            /** Format: int32 */
+            game_point: number;
+            /**
+             * Format: float
+             * @description What the result earns under the season's scoring
+             */
+            points: number;
+            /** Format: float */
+            table_point: number;
+            /** Format: date */
+            tournament_date: string;
+            /** Format: int32 */
+            tournament_id: number;
+            tournament_name: string;
+            /** Format: int32 */
+            tournament_place: number;
+        };
+        /**
+         * @description A player's starting wind at the table
+         * @enum {string}
+         */
+        SeatWind: "east" | "south" | "west" | "north";
+        /** @description A round of a tournament. */
+        Session: {
+            /** Format: int64 */
+            game_count: number;
+            /** Format: int32 */
+            id: number;
+            name: string;
+            /**
+             * Format: int32
+             * @description Position of the round within its tournament, from 1
+             */
+            round_number: number;
+            /** Format: int32 */
+            tournament_id: number;
+        };
+        SessionDetail: {
+            games: components["schemas"]["GameInfo"][];
+            info: components["schemas"]["SessionInfo"];
+        };
+        SessionInfo: {
+            /** Format: int32 */
+            id: number;
+            name: string;
+            /**
+             * Format: int32
+             * @description Position of the round within its tournament, from 1
+             */
+            round_number: number;
+        };
+        /** @description Circumstances of a win that the tiles alone do not show. */
+        Situation: {
+            /** @description Won on a tile added to another player's kan */
+            chankan?: boolean;
+            chiihou?: boolean;
+            double_riichi?: boolean;
+            /** Format: int32 */
+            honba?: number;
+            ippatsu?: boolean;
+            /** @description Round 4 han 30 fu and 3 han 60 fu up to a mangan */
+            kiriage_mangan?: boolean;
+            /** @description Won on the last tile of the wall (haitei) or its discard (houtei) */
+            last_tile?: boolean;
+            riichi?: boolean;
+            /**
+             * Format: int32
+             * @description Riichi deposits the winner collects from the table
+             */
+            riichi_sticks?: number;
+            /** @description Won on the replacement tile after a kan */
+            rinshan?: boolean;
+            round_wind: components["schemas"]["SeatWind"];
+            seat_wind: components["schemas"]["SeatWind"];
+            tenhou?: boolean;
+            win: components["schemas"]["WinType"];
+        };
+        /** @enum {string} */
+        SortOrder: "asc" | "desc";
+        /**
+         * @description A player's standing over the whole scope, ranked by total place points
+         *     and then total game points.
+         */
+        StandingChange: {
+            current: components["schemas"]["PlayerStats"];
+            /** Format: int32 */
+            current_rank: number;
+            new: components["schemas"]["PlayerStats"];
+            /** Format: int32 */
+            new_rank: number;
+            /** Format: int32 */
+            player_id: number;
+            player_name: string;
+            /**
+             * Format: int32
+             * @description Positive when the player moves up under the new ruleset
+             */
+            rank_change: number;
+        };
+        /**
+         * @description A player's place in one tournament, ranked by total place points and
+         *     then total game points under each ruleset. The tournament page itself
+         *     ranks by table points, which uma and oka do not change.
+         */
+        SummaryChange: {
+            current: components["schemas"]["TournamentSummary"];
+            /** Format: int32 */
+            current_place: number;
+            /** Format: float */
+            current_place_point: number;
+            new: components["schemas"]["TournamentSummary"];
+            /** Format: int32 */
+            new_place: number;
+            /** Format: float */
+            new_place_point: number;
+            /**
+             * Format: int32
+             * @description Positive when the player moves up under the new ruleset
+             */
+            place_change: number;
+            /** Format: int32 */
+            player_id: number;
+            player_name: string;
+        };
+        /**
+         * @description How players finishing on the same score are placed.
+         * @enum {string}
+         */
+        TieHandling: "split" | "seat_order";
+        TotalPoint: {
+            /** Format: int32 */
+            game_point: number;
+            /** Format: float */
+            table_point: number;
+        };
+        Tournament: {
+            /** Format: date */
+            date: string;
+            /** Format: int32 */
+            id: number;
+            location: string;
+            name: string;
+            /** Format: int32 */
+            ruleset_id: number;
+            sub_name: string;
+        };
+        TournamentDetail: {
+            /** Format: int32 */
+            id: number;
+            info: components["schemas"]["Tournament"];
+            sessions: components["schemas"]["SessionDetail"][];
+            summary: components["schemas"]["TournamentSummary"][];
+        };
+        /**
+         * @description A tournament with headline figures only; full results come from
+         *     `/v1/tournaments/{tournament_id}`.
+         */
+        TournamentListItem: {
+            /** Format: int64 */
+            game_count: number;
+            /** Format: int32 */
+            id: number;
+            info: components["schemas"]["Tournament"];
+            /** Format: int64 */
+            player_count: number;
+            winner?: null | components["schemas"]["TournamentWinner"];
+        };
+        TournamentPage: {
+            items: components["schemas"]["TournamentListItem"][];
+            /** @description Pass as `cursor` to fetch the next page; absent on the last page */
+            next_cursor?: string | null;
+        };
+        TournamentRankingChange: {
+            rankings: components["schemas"]["SummaryChange"][];
+            /** Format: date */
+            tournament_date: string;
+            /** Format: int32 */
+            tournament_id: number;
+            tournament_name: string;
+        };
+        /** @enum {string} */
+        TournamentSort: "date" | "name";
+        TournamentSummary: {
+            /** Format: int32 */
+            player_id: number;
+            player_name: string;
+            round_point: components["schemas"]["RoundPoint"][];
+            total_point: components["schemas"]["TotalPoint"];
+            /** Format: int32 */
+            tournament_place: number;
+        };
+        TournamentWinner: {
+            /** Format: int32 */
+            player_id: number;
+            player_name: string;
+            total_point: components["schemas"]["TotalPoint"];
+        };
+        UpdatePlayerRequest: {
+            name: string;
+        };
+        UpdateSeasonRequest: {
+            /** Format: int32 */
+            drop_worst?: number | null;
+            league_points?: number[] | null;
+            /** Format: int32 */
+            min_attendance?: number | null;
+            name?: string | null;
+            scoring?: null | components["schemas"]["SeasonScoring"];
+            /** @description Replaces the season's tournaments */
+            tournament_ids?: number[] | null;
+        };
+        UpdateSessionRequest: {
+            name?: string | null;
+            /**
+             * Format: int32
+             * @description Must not be taken by another round of the tournament
+             */
+            round_number?: number | null;
+        };
+        UpdateTournamentRequest: {
+            /** Format: date */
+            date?: string | null;
+            location?: string | null;
+            name?: string | null;
+            /**
+             * Format: int32
+             * @description Changing the ruleset rescores every game of the tournament
+             */
+            ruleset_id?: number | null;
+            sub_name?: string | null;
+        };
+        Violation: {
+            code: string;
+            field: string;
+            message: string;
+        };
+        /** @enum {string} */
+        Wait: "ryanmen" | "kanchan" | "penchan" | "shanpon" | "tanki";
+        WhatIfRequest: {
+            ruleset: components["schemas"]["RulesetDefinition"];
+            scope: components["schemas"]["WhatIfScope"];
+        };
+        WhatIfResponse: {
+            /** @description Ordered by new rank */
+            standings: components["schemas"]["StandingChange"][];
+            /** @description Tournaments in the scope by date, each ordered by new place */
+            tournaments: components["schemas"]["TournamentRankingChange"][];
+        };
+        /** @description Which games a what-if recalculation covers. */
+        WhatIfScope: {
+            /** Format: int32 */
+            tournament_id: number;
+            /** @enum {string} */
+            type: "tournament";
+        } | {
+            sub_name: string;
+            /** @enum {string} */
+            type: "series";
+        } | {
+            /** @enum {string} */
+            type: "all_time";
+        };
+        /** @enum {string} */
+        WinType: "ron" | "tsumo";
+        /** @enum {string} */
+        Yaku: "riichi" | "double_riichi" | "ippatsu" | "menzen_tsumo" | "pinfu" | "tanyao" | "iipeikou" | "haitei" | "houtei" | "rinshan_kaihou" | "chankan" | "haku" | "hatsu" | "chun" | "jikaze" | "bakaze" | "sanshoku_doujun" | "ittsu" | "chanta" | "chiitoitsu" | "toitoi" | "sanankou" | "sanshoku_doukou" | "sankantsu" | "shousangen" | "honroutou" | "junchan" | "ryanpeikou" | "honitsu" | "chinitsu" | "kokushi_musou" | "suuankou" | "daisangen" | "shousuushii" | "daisuushii" | "tsuuiisou" | "chinroutou" | "ryuuiisou" | "chuuren_poutou" | "suukantsu" | "tenhou" | "chiihou";
+        YakuHan: {
+            /** Format: int32 */
+            han: number;
+            yaku: components["schemas"]["Yaku"];
+        };
+    };
+    responses: never;
+    parameters: never;
+    requestBodies: never;
+    headers: never;
+    pathItems: never;
+}
+export type $defs = Record<string, never>;
+export interface operations {
+    health: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description The process is up */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    health_ready: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description Ready to serve requests */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+            /** @description Database unreachable */
+            503: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    root: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description Welcome message */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "text/plain": string;
+                };
+            };
+        };
+    };
+    login: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["LoginRequest"];
+            };
+        };
+        responses: {
+            /** @description Signed in */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["LoginResponse"];
+                };
+            };
+            /** @description Malformed request body */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Unknown user or wrong password */
+            401: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Unexpected server error */
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    me: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description The signed-in user */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["AuthUser"];
+                };
+            };
+            /** @description Missing or invalid token */
+            401: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    create_game: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["CreateGameRequest"];
+            };
+        };
+        responses: {
+            /** @description Game created */
+            201: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "text/plain": number;
+                };
+            };
+            /** @description Malformed request body */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+            /** @description Missing or invalid token */
+            401: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Scorekeeper role required */
+            403: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Game failed validation or references an unknown tournament or player */
+            422: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Unexpected server error */
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    get_game: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Game ID */
+                game_id: number;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description Game with its player results */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["GameRecord"];
+                };
+            };
+            /** @description Invalid path parameter */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Game not found */
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Unexpected server error */
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    update_game: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Game ID */
+                game_id: number;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["CreateGameRequest"];
+            };
+        };
+        responses: {
+            /** @description Game replaced */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["GameRecord"];
+                };
+            };
+            /** @description Malformed request body or path parameter */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Missing or invalid token */
+            401: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Scorekeeper role required */
+            403: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Game not found */
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Game failed validation or references an unknown tournament or player */
+            422: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Unexpected server error */
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    delete_game: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Game ID */
+                game_id: number;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description Game deleted */
+            204: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+            /** @description Invalid path parameter */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Missing or invalid token */
+            401: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Admin role required */
+            403: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Game not found */
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Unexpected server error */
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    get_player_stats_list: {
+        parameters: {
+            query?: {
+                /** @description Only games from tournaments on or after this date */
+                date_from?: string;
+                /** @description Only games from tournaments on or before this date */
+                date_to?: string;
+                /** @description Comma-separated tournament IDs, e.g. `3,4,5` */
+                tournament_ids?: string;
+                /** @description Only games from tournaments of this series */
+                sub_name?: string;
+                /** @description Leave out players with fewer games than this within the filter */
+                min_games?: number;
+                /**
+                 * @description Leave out players with fewer logged hands than this within the
+                 *     filter; use it with the hand columns, whose rates mean little over a
+                 *     few hands
+                 */
+                min_hands?: number;
+                /** @description Sort column; ties are broken by player_id */
+                sort?: components["schemas"]["PlayerStatsSort"];
+                /** @description Defaults to ascending for player_id and player_name, descending otherwise */
+                order?: components["schemas"]["SortOrder"];
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description 玩家统计 */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["PlayerStats"][];
+                };
+            };
+            /** @description Invalid filter */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Unexpected server error */
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    get_player_stats: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description 玩家ID */
+                player_id: number;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description 玩家统计和比赛详情 */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["PlayerStatsWithGames"];
+                };
+            };
+            /** @description Invalid path parameter */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description 玩家未找到 */
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Unexpected server error */
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    get_players: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description Player list */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Player"][];
+                };
+            };
+            /** @description Unexpected server error */
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    create_player: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["CreatePlayerRequest"];
+            };
+        };
+        responses: {
+            /** @description Player created */
+            201: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Player"];
+                };
+            };
+            /** @description Malformed request body */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Missing or invalid token */
+            401: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Scorekeeper role required */
+            403: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description A player with this name already exists */
+            409: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Invalid player name */
+            422: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Unexpected server error */
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    delete_player: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Player ID */
+                player_id: number;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description Player deleted */
+            204: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+            /** @description Invalid path parameter */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Missing or invalid token */
+            401: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Admin role required */
+            403: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Player not found */
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Player has recorded games and cannot be deleted */
+            409: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Unexpected server error */
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    update_player: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Player ID */
+                player_id: number;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["UpdatePlayerRequest"];
+            };
+        };
+        responses: {
+            /** @description Player renamed */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Player"];
+                };
+            };
+            /** @description Malformed request body or path parameter */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Missing or invalid token */
+            401: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Scorekeeper role required */
+            403: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Player not found */
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description A player with this name already exists */
+            409: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Invalid player name */
+            422: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Unexpected server error */
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    get_opponent_summary: {
+        parameters: {
+            query?: {
+                /** @description Only consider opponents met at least this many times (default 1) */
+                min_games?: number;
+                /** @description Opponents listed in each of best and worst (default 3) */
+                limit?: number;
+            };
+            header?: never;
+            path: {
+                /** @description Player ID */
+                player_id: number;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description The player's best and worst opponents by relative placement */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["OpponentSummary"];
+                };
+            };
+            /** @description Invalid min_games or limit */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Player not found */
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Unexpected server error */
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    get_rating_history: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Player ID */
+                player_id: number;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description The player's rating after each game, oldest first */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["RatingHistoryEntry"][];
+                };
+            };
+            /** @description Invalid path parameter */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Player not found */
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Unexpected server error */
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    get_head_to_head: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Player ID */
+                player_id: number;
+                /** @description Opponent's player ID */
+                opponent_id: number;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description Record between the two players and the games they shared */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["HeadToHead"];
+                };
+            };
+            /** @description Both IDs are the same player */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Player not found */
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Unexpected server error */
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    get_rating_leaderboard: {
+        parameters: {
+            query?: {
+                /** @description Leave out players with fewer rated games than this */
+                min_games?: number;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description Current rating of every rated player, by conservative rating */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["RatingLeaderboardEntry"][];
+                };
+            };
+            /** @description Invalid query string */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Unexpected server error */
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    get_rulesets: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description Ruleset list */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Ruleset"][];
+                };
+            };
+            /** @description Unexpected server error */
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    create_ruleset: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["CreateRulesetRequest"];
+            };
+        };
+        responses: {
+            /** @description Ruleset created */
+            201: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Ruleset"];
+                };
+            };
+            /** @description Malformed request body */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Missing or invalid token */
+            401: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Admin role required */
+            403: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Ruleset name already taken */
+            409: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Ruleset failed validation */
+            422: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Unexpected server error */
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    get_seasons: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description Season list */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Season"][];
+                };
+            };
+            /** @description Unexpected server error */
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    create_season: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["CreateSeasonRequest"];
+            };
+        };
+        responses: {
+            /** @description Season created */
+            201: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Season"];
+                };
+            };
+            /** @description Malformed request body */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Missing or invalid token */
+            401: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Scorekeeper role required */
+            403: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Season name already taken */
+            409: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Season failed validation or references an unknown tournament */
+            422: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Unexpected server error */
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    delete_season: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Season ID */
+                season_id: number;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description Season deleted; its tournaments are kept */
+            204: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+            /** @description Invalid path parameter */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Missing or invalid token */
+            401: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Admin role required */
+            403: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Season not found */
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Unexpected server error */
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    update_season: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Season ID */
+                season_id: number;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["UpdateSeasonRequest"];
+            };
+        };
+        responses: {
+            /** @description Season updated */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Season"];
+                };
+            };
+            /** @description Malformed request body or path parameter */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Missing or invalid token */
+            401: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Scorekeeper role required */
+            403: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Season not found */
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Season name already taken */
+            409: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Season failed validation or references an unknown tournament */
+            422: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Unexpected server error */
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    get_season_standings: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Season ID */
+                season_id: number;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description Season standings */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["SeasonStandings"];
+                };
+            };
+            /** @description Invalid path parameter */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Season not found */
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Unexpected server error */
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    delete_session: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Session ID */
+                session_id: number;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description Session deleted */
+            204: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+            /** @description Invalid path parameter */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Missing or invalid token */
+            401: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Admin role required */
+            403: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Session not found */
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Session still has games */
+            409: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Unexpected server error */
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    update_session: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Session ID */
+                session_id: number;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["UpdateSessionRequest"];
+            };
+        };
+        responses: {
+            /** @description Session updated */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Session"];
+                };
+            };
+            /** @description Malformed request body or path parameter */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Missing or invalid token */
+            401: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Scorekeeper role required */
+            403: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Session not found */
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Round number or name already taken in the tournament */
+            409: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Session failed validation */
+            422: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Unexpected server error */
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    evaluate_hand: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["EvaluateRequest"];
+            };
+        };
+        responses: {
+            /** @description Every reading of the hand and the best one */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Evaluation"];
+                };
+            };
+            /** @description Malformed request body */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Invalid tiles, melds or situation */
+            422: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    calculate_score: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["ScoreRequest"];
+            };
+        };
+        responses: {
+            /** @description Hand value and payments */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ScoreResult"];
+                };
+            };
+            /** @description Malformed request body */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Invalid han, fu or counters */
+            422: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    get_tournaments: {
+        parameters: {
+            query?: {
+                /** @description Only tournaments held in this calendar year */
+                year?: number;
+                /** @description Only tournaments of this series */
+                sub_name?: string;
+                location?: string;
+                /** @description Sort column; ties are broken by id */
+                sort?: components["schemas"]["TournamentSort"];
+                order?: components["schemas"]["SortOrder"];
+                /** @description Page size, 1 to 100; defaults to 20 */
+                limit?: number;
+                /** @description `next_cursor` from the previous page */
+                cursor?: string;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description One page of tournaments with headline figures */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["TournamentPage"];
+                };
+            };
+            /** @description Invalid limit or cursor */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Unexpected server error */
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    create_tournament: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["CreateTournamentRequest"];
+            };
+        };
+        responses: {
+            /** @description Tournament created */
+            201: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Tournament"];
+                };
+            };
+            /** @description Malformed request body */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+            /** @description Missing or invalid token */
+            401: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Scorekeeper role required */
+            403: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Unknown ruleset */
+            422: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Unexpected server error */
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    get_tournament: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Tournament ID */
+                tournament_id: number;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description Tournament detail */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["TournamentDetail"];
+                };
+            };
+            /** @description Invalid path parameter */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Tournament not found */
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Unexpected server error */
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    delete_tournament: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Tournament ID */
+                tournament_id: number;
+                /** @description Also delete the tournament's sessions and games */
+                force: boolean;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description Tournament deleted */
+            204: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+            /** @description Invalid query string or path parameter */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Missing or invalid token */
+            401: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Admin role required */
+            403: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Tournament not found */
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Tournament has games; pass force=true to delete them too */
+            409: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Unexpected server error */
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    update_tournament: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Tournament ID */
+                tournament_id: number;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["UpdateTournamentRequest"];
+            };
+        };
+        responses: {
+            /** @description Tournament updated */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Tournament"];
+                };
+            };
+            /** @description Malformed request body or path parameter */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Missing or invalid token */
+            401: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Scorekeeper role required */
+            403: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Tournament not found */
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Games whose table points do not follow their game points block a ruleset change */
+            409: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Unknown ruleset */
+            422: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Unexpected server error */
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    get_sessions: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Tournament ID */
+                tournament_id: number;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description Rounds of the tournament in order */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Session"][];
+                };
+            };
+            /** @description Invalid path parameter */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Tournament not found */
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Unexpected server error */
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    create_session: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Tournament ID */
+                tournament_id: number;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["CreateSessionRequest"];
+            };
+        };
+        responses: {
+            /** @description Session created */
+            201: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Session"];
+                };
+            };
+            /** @description Malformed request body or path parameter */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Missing or invalid token */
+            401: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Scorekeeper role required */
+            403: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
             /** @description Tournament not found */
             404: {
                 headers: {
                     [name: string]: unknown;
                 };
-                content?: never;
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Round number or name already taken in the tournament */
+            409: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Session failed validation */
+            422: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Unexpected server error */
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    reorder_sessions: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Tournament ID */
+                tournament_id: number;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["ReorderSessionsRequest"];
+            };
+        };
+        responses: {
+            /** @description Rounds of the tournament in their new order */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Session"][];
+                };
+            };
+            /** @description Malformed request body or path parameter */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Missing or invalid token */
+            401: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Scorekeeper role required */
+            403: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Tournament not found */
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description session_ids is not exactly the tournament's sessions */
+            422: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Unexpected server error */
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    get_users: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description User list */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["AuthUser"][];
+                };
+            };
+            /** @description Missing or invalid token */
+            401: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Admin role required */
+            403: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Unexpected server error */
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    create_user: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["CreateUserRequest"];
+            };
+        };
+        responses: {
+            /** @description User created */
+            201: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["AuthUser"];
+                };
+            };
+            /** @description Malformed request body */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Missing or invalid token */
+            401: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Admin role required */
+            403: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Username already taken */
+            409: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Invalid username or password */
+            422: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Unexpected server error */
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+        };
+    };
+    what_if: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["WhatIfRequest"];
+            };
+        };
+        responses: {
+            /** @description Rankings under the current rulesets and under the given one */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["WhatIfResponse"];
+                };
+            };
+            /** @description Malformed request body */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Tournament not found */
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Ruleset failed validation */
+            422: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
+            };
+            /** @description Unexpected server error */
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/problem+json": components["schemas"]["Problem"];
+                };
             };
         };
     };